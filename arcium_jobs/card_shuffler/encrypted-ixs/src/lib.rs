@@ -6,12 +6,20 @@ use arcis_imports::*;
 mod circuits {
     use arcis_imports::*;
 
-    /// Standard 52-card deck represented as indices 0-51
-    const INITIAL_DECK: [u8; 52] = [
-        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
-        25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47,
-        48, 49, 50, 51,
-    ];
+    /// Maximum number of copies of the base deck in a shoe
+    const MAX_DECK_COPIES: usize = 6;
+    /// Maximum number of jokers in a shoe (two per copy)
+    const MAX_JOKERS: usize = 2 * MAX_DECK_COPIES;
+    /// Maximum number of cards in a deck: six 52-card copies plus their jokers
+    const MAX_DECK_SIZE: usize = 52 * MAX_DECK_COPIES + MAX_JOKERS;
+    /// Number of u128 limbs needed to pack MAX_DECK_SIZE cards (21 cards per limb)
+    const DECK_LIMBS: usize = 16;
+    /// Card index used for a joker
+    const JOKER: u8 = 52;
+    /// Marks a deck slot that holds no card (beyond the configured deck size)
+    const DECK_PADDING: u8 = 63;
+    /// Number of player seed slots a shuffle takes
+    const MAX_ENTROPY_SEEDS: usize = 4;
 
     /// Powers of 64 used for encoding cards into u128 values.
     /// Each card takes 6 bits (values 0-63), so we can pack multiple cards efficiently.
@@ -40,66 +48,75 @@ mod circuits {
         1329227995784915872903807060280344576,
     ];
 
-    /// Represents a full 52-card deck encoded into three u128 values for efficiency.
+    /// Represents a deck of up to MAX_DECK_SIZE cards encoded into u128 limbs for efficiency.
     ///
-    /// Each card is represented by 6 bits (0-63 range), allowing us to pack:
-    /// - Cards 0-20 in card_one (21 cards × 6 bits = 126 bits < 128 bits)
-    /// - Cards 21-41 in card_two (21 cards × 6 bits = 126 bits < 128 bits)
-    /// - Cards 42-51 in card_three (10 cards × 6 bits = 60 bits < 128 bits)
+    /// Each card is represented by 6 bits (0-63 range), so each limb packs 21 cards
+    /// (21 cards × 6 bits = 126 bits < 128 bits). Slots past the configured deck size
+    /// hold DECK_PADDING.
     pub struct Deck {
-        pub card_one: u128,
-        pub card_two: u128,
-        pub card_three: u128,
+        pub cards: [u128; DECK_LIMBS],
     }
 
     impl Deck {
-        /// Converts a 52-card array into the packed Deck representation.
+        /// Converts a card array into the packed Deck representation.
         /// Uses base-64 encoding where each card index is treated as a digit in base 64.
-        pub fn from_array(array: [u8; 52]) -> Deck {
-            let mut card_one = 0;
-            for i in 0..21 {
-                card_one += POWS_OF_SIXTY_FOUR[i] * array[i] as u128;
+        pub fn from_array(array: [u8; MAX_DECK_SIZE]) -> Deck {
+            let mut cards = [0u128; DECK_LIMBS];
+            for i in 0..MAX_DECK_SIZE {
+                cards[i / 21] += POWS_OF_SIXTY_FOUR[i % 21] * array[i] as u128;
             }
 
-            let mut card_two = 0;
-            for i in 21..42 {
-                card_two += POWS_OF_SIXTY_FOUR[i - 21] * array[i] as u128;
-            }
+            Deck { cards }
+        }
 
-            let mut card_three = 0;
-            for i in 42..52 {
-                card_three += POWS_OF_SIXTY_FOUR[i - 42] * array[i] as u128;
+        /// Converts the packed Deck representation back to a card array.
+        /// Reverses the base-64 encoding by extracting 6 bits at a time.
+        fn to_array(&self) -> [u8; MAX_DECK_SIZE] {
+            let mut bytes = [DECK_PADDING; MAX_DECK_SIZE];
+            for limb in 0..DECK_LIMBS {
+                let mut cards = self.cards[limb];
+                for j in 0..21 {
+                    let i = limb * 21 + j;
+                    if i < MAX_DECK_SIZE {
+                        bytes[i] = (cards % 64) as u8;
+                    }
+                    cards >>= 6;
+                }
             }
 
-            Deck {
-                card_one,
-                card_two,
-                card_three,
-            }
+            bytes
         }
+    }
 
-        /// Converts the packed Deck representation back to a 52-card array.
-        /// Reverses the base-64 encoding by extracting 6 bits at a time.
-        fn to_array(&self) -> [u8; 52] {
-            let mut card_one = self.card_one;
-            let mut card_two = self.card_two;
-            let mut card_three = self.card_three;
-
-            let mut bytes = [0u8; 52];
-            for i in 0..21 {
-                bytes[i] = (card_one % 64) as u8;
-                bytes[i + 21] = (card_two % 64) as u8;
-                card_one >>= 6;
-                card_two >>= 6;
+    /// Lays out the cards of a deck specification in order, followed by padding.
+    ///
+    /// - `rank_mask`: bit r includes rank r (0 = two ... 12 = ace)
+    /// - `suit_mask`: bit s includes suit s (clubs, diamonds, hearts, spades)
+    /// - `copies`: number of copies of the selected cards
+    /// - `jokers`: number of jokers added to the shoe
+    fn build_deck(rank_mask: u16, suit_mask: u8, copies: u8, jokers: u8) -> [u8; MAX_DECK_SIZE] {
+        let mut deck = [DECK_PADDING; MAX_DECK_SIZE];
+        let mut size = 0;
+        for copy in 0..MAX_DECK_COPIES {
+            for card in 0..52 {
+                let suit = card / 13;
+                let rank = card % 13;
+                if copy < copies as usize
+                    && ((suit_mask >> suit) & 1) == 1
+                    && ((rank_mask >> rank) & 1) == 1
+                {
+                    deck[size] = card as u8;
+                    size += 1;
+                }
             }
-
-            for i in 42..52 {
-                bytes[i] = (card_three % 64) as u8;
-                card_three >>= 6;
+        }
+        for j in 0..MAX_JOKERS {
+            if j < jokers as usize {
+                deck[size] = JOKER;
+                size += 1;
             }
-
-            bytes
         }
+        deck
     }
 
     /// Represents a hand of up to 11 cards encoded into a single u128.
@@ -131,56 +148,691 @@ mod circuits {
         }
     }
 
-    /// Shuffles a deck and deals initial hole cards.
+    /// Bit position of the hand category within a poker hand score.
+    /// The 20 bits below it hold up to five tie-breaking ranks, four bits each.
+    const HAND_CATEGORY_SHIFT: u32 = 20;
+
+    /// Splits a card index (suit * 13 + rank) into its rank and suit.
+    /// Uses comparisons instead of division, which is much cheaper inside MPC.
+    fn rank_and_suit(card: u8) -> (u8, u8) {
+        let mut suit = 0;
+        if card >= 13 {
+            suit += 1;
+        }
+        if card >= 26 {
+            suit += 1;
+        }
+        if card >= 39 {
+            suit += 1;
+        }
+        (card - suit * 13, suit)
+    }
+
+    /// Finds the highest straight among the present ranks (ace plays high or low).
+    fn straight_high(present: [bool; 13]) -> (bool, u32) {
+        let mut found = false;
+        let mut high = 0;
+
+        // Wheel: A-2-3-4-5 is a five-high straight
+        if present[12] && present[0] && present[1] && present[2] && present[3] {
+            found = true;
+            high = 3;
+        }
+
+        for h in 4..13 {
+            if present[h - 4] && present[h - 3] && present[h - 2] && present[h - 1] && present[h] {
+                found = true;
+                high = h as u32;
+            }
+        }
+
+        (found, high)
+    }
+
+    /// Packs the `n` highest present ranks (skipping up to two excluded ranks)
+    /// into four-bit digits, highest first. Pass 13 to exclude nothing.
+    fn top_ranks(present: [bool; 13], exclude_a: u32, exclude_b: u32, n: u8) -> u32 {
+        let mut kickers = 0;
+        let mut taken = 0;
+        for i in 0..13 {
+            let rank = 12 - i;
+            if present[rank]
+                && rank as u32 != exclude_a
+                && rank as u32 != exclude_b
+                && taken < n
+            {
+                kickers = kickers * 16 + rank as u32;
+                taken += 1;
+            }
+        }
+        kickers
+    }
+
+    /// Scores the best five-card poker hand that can be made from seven cards.
+    /// A higher score is a better hand; equal scores split the pot.
+    ///
+    /// Categories (score >> HAND_CATEGORY_SHIFT):
+    /// 0 high card, 1 pair, 2 two pair, 3 three of a kind, 4 straight,
+    /// 5 flush, 6 full house, 7 four of a kind, 8 straight flush
+    fn poker_hand_score(cards: [u8; 7]) -> u32 {
+        let mut ranks = [0u8; 7];
+        let mut suits = [0u8; 7];
+        let mut rank_counts = [0u8; 13];
+        let mut suit_counts = [0u8; 4];
+        for c in 0..7 {
+            let (rank, suit) = rank_and_suit(cards[c]);
+            ranks[c] = rank;
+            suits[c] = suit;
+            for r in 0..13 {
+                if cards[c] < 52 && rank == r as u8 {
+                    rank_counts[r] += 1;
+                }
+            }
+            for s in 0..4 {
+                if cards[c] < 52 && suit == s as u8 {
+                    suit_counts[s] += 1;
+                }
+            }
+        }
+
+        // Flush and straight flush
+        let mut has_flush = false;
+        let mut flush_suit = 0;
+        for s in 0..4 {
+            if suit_counts[s] >= 5 {
+                has_flush = true;
+                flush_suit = s as u8;
+            }
+        }
+        let mut flush_present = [false; 13];
+        for c in 0..7 {
+            for r in 0..13 {
+                if cards[c] < 52 && suits[c] == flush_suit && ranks[c] == r as u8 {
+                    flush_present[r] = true;
+                }
+            }
+        }
+        let (has_straight_flush, straight_flush_high) = straight_high(flush_present);
+
+        // Rank groups, highest rank wins within each group size
+        let mut present = [false; 13];
+        let mut has_quads = false;
+        let mut quads = 0;
+        let mut has_trips = false;
+        let mut trips = 0;
+        let mut has_second_trips = false;
+        let mut second_trips = 0;
+        let mut has_pair = false;
+        let mut pair = 0;
+        let mut has_second_pair = false;
+        let mut second_pair = 0;
+        for r in 0..13 {
+            let count = rank_counts[r];
+            present[r] = count > 0;
+            if count == 4 {
+                has_quads = true;
+                quads = r as u32;
+            }
+            if count == 3 {
+                has_second_trips = has_trips;
+                second_trips = trips;
+                has_trips = true;
+                trips = r as u32;
+            }
+            if count == 2 {
+                has_second_pair = has_pair;
+                second_pair = pair;
+                has_pair = true;
+                pair = r as u32;
+            }
+        }
+        let (has_straight, straight_high_rank) = straight_high(present);
+
+        // A full house uses the best pair, or the lower of two trips
+        let mut has_full_house_pair = false;
+        let mut full_house_pair = 0;
+        if has_pair {
+            has_full_house_pair = true;
+            full_house_pair = pair;
+        }
+        if has_second_trips && (!has_pair || second_trips > pair) {
+            has_full_house_pair = true;
+            full_house_pair = second_trips;
+        }
+
+        // Each better category overrides the previous one
+        let mut score = top_ranks(present, 13, 13, 5);
+        if has_pair {
+            score = (1 << HAND_CATEGORY_SHIFT) + pair * 4096 + top_ranks(present, pair, 13, 3);
+        }
+        if has_second_pair {
+            score = (2 << HAND_CATEGORY_SHIFT)
+                + pair * 256
+                + second_pair * 16
+                + top_ranks(present, pair, second_pair, 1);
+        }
+        if has_trips {
+            score = (3 << HAND_CATEGORY_SHIFT) + trips * 256 + top_ranks(present, trips, 13, 2);
+        }
+        if has_straight {
+            score = (4 << HAND_CATEGORY_SHIFT) + straight_high_rank;
+        }
+        if has_flush {
+            score = (5 << HAND_CATEGORY_SHIFT) + top_ranks(flush_present, 13, 13, 5);
+        }
+        if has_trips && has_full_house_pair {
+            score = (6 << HAND_CATEGORY_SHIFT) + trips * 16 + full_house_pair;
+        }
+        if has_quads {
+            score = (7 << HAND_CATEGORY_SHIFT) + quads * 16 + top_ranks(present, quads, 13, 1);
+        }
+        if has_straight_flush {
+            score = (8 << HAND_CATEGORY_SHIFT) + straight_flush_high;
+        }
+
+        score
+    }
+
+    /// Blackjack value of a single card: faces count 10 and aces count 1
+    /// (`blackjack_total` decides whether an ace is promoted to 11).
+    fn blackjack_card_value(card: u8) -> u8 {
+        let (rank, _) = rank_and_suit(card);
+        let mut value = rank + 2;
+        if rank >= 8 {
+            value = 10;
+        }
+        if rank == 12 {
+            value = 1;
+        }
+        value
+    }
+
+    /// Baccarat value of a single card: tens and faces count 0, aces count 1
+    fn baccarat_card_value(card: u8) -> u8 {
+        let (rank, _) = rank_and_suit(card);
+        let mut value = rank + 2;
+        if rank >= 8 {
+            value = 0;
+        }
+        if rank == 12 {
+            value = 1;
+        }
+        value
+    }
+
+    /// Adds a card to a baccarat total, keeping only the last digit
+    fn baccarat_add(total: u8, card: u8) -> u8 {
+        let mut sum = total + baccarat_card_value(card);
+        if sum >= 10 {
+            sum -= 10;
+        }
+        sum
+    }
+
+    /// Computes a blackjack hand total, counting one ace as 11 when that does
+    /// not bust the hand. Empty slots and jokers are ignored.
+    ///
+    /// Returns the total and whether it is soft (an ace is being counted as 11).
+    fn blackjack_total(hand: [u8; 11]) -> (u8, bool) {
+        let mut total = 0;
+        let mut has_ace = false;
+        for i in 0..11 {
+            if hand[i] < 52 {
+                let value = blackjack_card_value(hand[i]);
+                total += value;
+                if value == 1 {
+                    has_ace = true;
+                }
+            }
+        }
+
+        let mut soft = false;
+        if has_ace && total + 10 <= 21 {
+            total += 10;
+            soft = true;
+        }
+
+        (total, soft)
+    }
+
+    /// A 32-bit word as bits, least significant first. Bitwise SHA-256 keeps the
+    /// hash inside MPC without revealing anything but the digest.
+    type Word = [bool; 32];
+
+    /// Bytes hashed into a deck commitment: the 16-byte salt followed by each
+    /// deck limb, all little endian
+    const COMMITMENT_MESSAGE_BYTES: usize = 16 * (DECK_LIMBS + 1);
+    /// Bytes hashed into a chained deck commitment: the previous commitment
+    /// followed by the same message as an unchained one
+    const CHAINED_COMMITMENT_MESSAGE_BYTES: usize = 32 + COMMITMENT_MESSAGE_BYTES;
+    /// SHA-256 blocks needed for the longest message plus its 0x80 byte and
+    /// 64-bit length
+    const COMMITMENT_BLOCKS: usize = (CHAINED_COMMITMENT_MESSAGE_BYTES + 9 + 63) / 64;
+
+    /// SHA-256 initial hash value
+    const SHA256_INITIAL: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    /// SHA-256 round constants
+    const SHA256_ROUND_CONSTANTS: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+
+    fn word_from_u32(value: u32) -> Word {
+        let mut word = [false; 32];
+        for i in 0..32 {
+            word[i] = (value >> i) & 1 == 1;
+        }
+        word
+    }
+
+    fn word_add(a: Word, b: Word) -> Word {
+        let mut sum = [false; 32];
+        let mut carry = false;
+        for i in 0..32 {
+            let half = a[i] != b[i];
+            sum[i] = half != carry;
+            carry = (a[i] && b[i]) || (carry && half);
+        }
+        sum
+    }
+
+    fn word_xor(a: Word, b: Word) -> Word {
+        let mut out = [false; 32];
+        for i in 0..32 {
+            out[i] = a[i] != b[i];
+        }
+        out
+    }
+
+    fn word_rotr(word: Word, n: usize) -> Word {
+        let mut out = [false; 32];
+        for i in 0..32 {
+            out[i] = word[(i + n) % 32];
+        }
+        out
+    }
+
+    fn word_shr(word: Word, n: usize) -> Word {
+        let mut out = [false; 32];
+        for i in 0..32 {
+            if i + n < 32 {
+                out[i] = word[i + n];
+            }
+        }
+        out
+    }
+
+    /// Splits a value into its bits, least significant first
+    fn u128_to_bits(value: u128) -> [bool; 128] {
+        let mut value = value;
+        let mut bits = [false; 128];
+        for i in 0..128 {
+            bits[i] = value % 2 == 1;
+            value >>= 1;
+        }
+        bits
+    }
+
+    /// SHA-256 compression of one 512-bit block (bits in SHA-256 order) into `state`
+    fn sha256_compress(state: [Word; 8], block: &[bool; 512]) -> [Word; 8] {
+        // Message schedule
+        let mut schedule = [[false; 32]; 64];
+        for t in 0..16 {
+            for i in 0..32 {
+                schedule[t][i] = block[t * 32 + 31 - i];
+            }
+        }
+        for t in 16..64 {
+            let s0 = word_xor(
+                word_xor(word_rotr(schedule[t - 15], 7), word_rotr(schedule[t - 15], 18)),
+                word_shr(schedule[t - 15], 3),
+            );
+            let s1 = word_xor(
+                word_xor(word_rotr(schedule[t - 2], 17), word_rotr(schedule[t - 2], 19)),
+                word_shr(schedule[t - 2], 10),
+            );
+            schedule[t] = word_add(
+                word_add(schedule[t - 16], s0),
+                word_add(schedule[t - 7], s1),
+            );
+        }
+
+        // Compression
+        let mut a = state[0];
+        let mut b = state[1];
+        let mut c = state[2];
+        let mut d = state[3];
+        let mut e = state[4];
+        let mut f = state[5];
+        let mut g = state[6];
+        let mut h = state[7];
+        for t in 0..64 {
+            let sigma1 = word_xor(
+                word_xor(word_rotr(e, 6), word_rotr(e, 11)),
+                word_rotr(e, 25),
+            );
+            let mut choose = [false; 32];
+            let mut majority = [false; 32];
+            for i in 0..32 {
+                choose[i] = if e[i] { f[i] } else { g[i] };
+                majority[i] = (a[i] && b[i]) || (c[i] && (a[i] != b[i]));
+            }
+            let temp1 = word_add(
+                word_add(word_add(h, sigma1), word_add(choose, schedule[t])),
+                word_from_u32(SHA256_ROUND_CONSTANTS[t]),
+            );
+            let sigma0 = word_xor(
+                word_xor(word_rotr(a, 2), word_rotr(a, 13)),
+                word_rotr(a, 22),
+            );
+            let temp2 = word_add(sigma0, majority);
+
+            h = g;
+            g = f;
+            f = e;
+            e = word_add(d, temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = word_add(temp1, temp2);
+        }
+
+        let working = [a, b, c, d, e, f, g, h];
+        let mut next = state;
+        for i in 0..8 {
+            next[i] = word_add(state[i], working[i]);
+        }
+        next
+    }
+
+    fn sha256_initial_state() -> [Word; 8] {
+        let mut state = [[false; 32]; 8];
+        for i in 0..8 {
+            state[i] = word_from_u32(SHA256_INITIAL[i]);
+        }
+        state
+    }
+
+    /// Writes `bits` (least significant first) into `message` as little-endian
+    /// bytes starting at byte `offset`, most significant bit of each byte first
+    fn write_le_bytes(message: &mut [bool], offset: usize, bits: &[bool]) {
+        for byte in 0..bits.len() / 8 {
+            for bit in 0..8 {
+                message[(offset + byte) * 8 + 7 - bit] = bits[byte * 8 + bit];
+            }
+        }
+    }
+
+    /// SHA-256 commitment to a deck order:
+    /// `sha256(salt || deck.cards[0] || ... || deck.cards[15])`, each value as
+    /// 16 little-endian bytes. Published with the salt by `reveal_deck`, so
+    /// anyone can recompute it off-chain.
+    fn deck_commitment(deck: &Deck, salt: u128) -> [u8; 32] {
+        // Message bits in SHA-256 order (bytes in sequence, most significant bit
+        // first)
+        let mut message = [false; COMMITMENT_BLOCKS * 512];
+        write_le_bytes(&mut message, 0, &u128_to_bits(salt));
+        for limb in 0..DECK_LIMBS {
+            write_le_bytes(&mut message, (limb + 1) * 16, &u128_to_bits(deck.cards[limb]));
+        }
+        sha256_message(message, COMMITMENT_MESSAGE_BYTES)
+    }
+
+    /// Commitment to a reshuffled deck order, chained to the one it replaces:
+    /// `sha256(previous || salt || deck.cards[0] || ... || deck.cards[15])`.
+    /// The chain runs back to the shuffle that dealt the first card, so an
+    /// audit of the final deck also ties it to every earlier commitment.
+    fn chained_deck_commitment(previous: [u8; 32], deck: &Deck, salt: u128) -> [u8; 32] {
+        let mut message = [false; COMMITMENT_BLOCKS * 512];
+        for byte in 0..32 {
+            for bit in 0..8 {
+                message[byte * 8 + 7 - bit] = (previous[byte] >> bit) & 1 == 1;
+            }
+        }
+        write_le_bytes(&mut message, 32, &u128_to_bits(salt));
+        for limb in 0..DECK_LIMBS {
+            write_le_bytes(&mut message, (limb + 3) * 16, &u128_to_bits(deck.cards[limb]));
+        }
+        sha256_message(message, CHAINED_COMMITMENT_MESSAGE_BYTES)
+    }
+
+    /// SHA-256 digest of the first `message_bytes` bytes of `message`, which
+    /// must leave room for the padding in COMMITMENT_BLOCKS blocks
+    fn sha256_message(
+        mut message: [bool; COMMITMENT_BLOCKS * 512],
+        message_bytes: usize,
+    ) -> [u8; 32] {
+        let blocks = (message_bytes + 9 + 63) / 64;
+        message[message_bytes * 8] = true;
+        let length_bits = (message_bytes * 8) as u64;
+        for bit in 0..64 {
+            message[blocks * 512 - 1 - bit] = (length_bits >> bit) & 1 == 1;
+        }
+
+        let mut state = sha256_initial_state();
+        for block in 0..COMMITMENT_BLOCKS {
+            if block < blocks {
+                let mut chunk = [false; 512];
+                for i in 0..512 {
+                    chunk[i] = message[block * 512 + i];
+                }
+                state = sha256_compress(state, &chunk);
+            }
+        }
+
+        // Digest bytes, each word big endian
+        let mut digest = [0u8; 32];
+        for i in 0..8 {
+            for byte in 0..4 {
+                let mut value = 0u8;
+                for bit in 0..8 {
+                    if state[i][(3 - byte) * 8 + bit] {
+                        value += 1 << bit;
+                    }
+                }
+                digest[i * 4 + byte] = value;
+            }
+        }
+        digest
+    }
+
+    /// Stages of the sorting network: the smallest s with 2^s >= MAX_DECK_SIZE
+    const SORT_STAGES: usize = 9;
+
+    /// XORs together the first `num_seeds` player seeds. The result is uniform as
+    /// long as any one of them is, and never leaves MPC.
+    fn seed_entropy(num_seeds: u8, seeds: [u128; MAX_ENTROPY_SEEDS]) -> [bool; 128] {
+        let mut entropy = [false; 128];
+        for seed in 0..MAX_ENTROPY_SEEDS {
+            let used = (seed as u8) < num_seeds;
+            let bits = u128_to_bits(seeds[seed]);
+            for i in 0..128 {
+                entropy[i] = entropy[i] != (used && bits[i]);
+            }
+        }
+        entropy
+    }
+
+    /// Permutes `deck` in a single pass by sorting it on secret 32-bit keys. The
+    /// key for slot `i` is a fresh `ArcisRNG` draw XORed with the 32 bits of
+    /// `entropy` starting at bit `32 * (i % 4) + i / 4` (wrapping around).
+    ///
+    /// If Arcium's RNG is uniform, every key is uniform and independent of the
+    /// players' seeds, so the permutation is uniform whatever they sent. If it is
+    /// not, the keys still depend on `entropy`, which is uniform as long as any
+    /// one player's seed is. Either way neither the cluster nor a player can
+    /// steer the order on their own. Equal keys (probability below 2^-16 for a
+    /// full shoe) keep their relative order.
+    ///
+    /// The sorting network is fixed, so only the keys (which never leave MPC)
+    /// decide where cards go.
+    fn keyed_shuffle(deck: &mut [u8; MAX_DECK_SIZE], entropy: [bool; 128]) {
+        let mut keys = [0u32; MAX_DECK_SIZE];
+        for slot in 0..MAX_DECK_SIZE {
+            let mut draw = ArcisRNG::gen_integer_from_width(32);
+            let mut key = 0u32;
+            for bit in 0..32 {
+                let mixed = (draw % 2 == 1) != entropy[(32 * (slot % 4) + slot / 4 + bit) % 128];
+                if mixed {
+                    key += 1 << bit;
+                }
+                draw >>= 1;
+            }
+            keys[slot] = key;
+        }
+
+        // Batcher's odd-even merge sort, which works for any deck length
+        for stage in 0..SORT_STAGES {
+            let p = 1 << stage;
+            for step in 0..SORT_STAGES {
+                if step <= stage {
+                    let k = p >> step;
+                    for j in 0..MAX_DECK_SIZE {
+                        if j >= k % p && (j - k % p) % (2 * k) == 0 && j + k < MAX_DECK_SIZE {
+                            for i in 0..MAX_DECK_SIZE {
+                                let lo = i + j;
+                                let hi = i + j + k;
+                                if i < k && hi < MAX_DECK_SIZE && lo / (2 * p) == hi / (2 * p) {
+                                    let swap = keys[lo] > keys[hi];
+                                    let (key_lo, key_hi) = (keys[lo], keys[hi]);
+                                    let (card_lo, card_hi) = (deck[lo], deck[hi]);
+                                    keys[lo] = if swap { key_hi } else { key_lo };
+                                    keys[hi] = if swap { key_lo } else { key_hi };
+                                    deck[lo] = if swap { card_hi } else { card_lo };
+                                    deck[hi] = if swap { card_lo } else { card_hi };
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Builds a deck from its specification, shuffles it and deals initial hole cards.
+    /// The shuffle mixes Arcium's RNG with the first `num_seeds` player seeds (see
+    /// `keyed_shuffle`); unused seed slots are ignored.
+    /// Commits to the shuffled order with a salted hash so the deck can be audited
+    /// once `reveal_deck` publishes it.
     ///
     /// Returns:
     /// - Encrypted deck (for MXE to manage subsequent draws)
     /// - Encrypted hole cards for player (shared encryption with client)
     /// - Number of cards dealt (to track deck position)
+    /// - Encrypted commitment salt (MXE only, until `reveal_deck`)
+    /// - Deck commitment (see `deck_commitment`)
     #[instruction]
     pub fn shuffle_and_deal_deck(
         mxe: Mxe,
+        salt_mxe: Mxe,
         client: Shared,
         num_hole_cards: u8,
+        rank_mask: u16,
+        suit_mask: u8,
+        copies: u8,
+        jokers: u8,
+        num_seeds: u8,
+        seed_0: Enc<Shared, u128>,
+        seed_1: Enc<Shared, u128>,
+        seed_2: Enc<Shared, u128>,
+        seed_3: Enc<Shared, u128>,
     ) -> (
         Enc<Mxe, Deck>,    // Encrypted deck
         Enc<Shared, Hand>, // Player hole cards
         u8,                // Number of cards dealt
+        Enc<Mxe, u128>,    // Commitment salt
+        [u8; 32],          // Deck commitment
     ) {
-        // Shuffle the deck using Arcium's RNG
-        let mut deck = INITIAL_DECK;
-        ArcisRNG::shuffle(&mut deck);
+        // Shuffle the deck (padding included) once, keyed by Arcium's RNG with
+        // the players' entropy mixed in
+        let mut padded_deck = build_deck(rank_mask, suit_mask, copies, jokers);
+        let seeds = [
+            seed_0.to_arcis(),
+            seed_1.to_arcis(),
+            seed_2.to_arcis(),
+            seed_3.to_arcis(),
+        ];
+        keyed_shuffle(&mut padded_deck, seed_entropy(num_seeds, seeds));
+
+        // Move the real cards to the front. The padding lands in uniformly random
+        // slots independently of the card order, so revealing where it went leaks
+        // nothing about the cards themselves.
+        let mut is_padding = [false; MAX_DECK_SIZE];
+        for i in 0..MAX_DECK_SIZE {
+            is_padding[i] = (padded_deck[i] == DECK_PADDING).reveal();
+        }
+        let mut deck = [DECK_PADDING; MAX_DECK_SIZE];
+        let mut deck_size = 0;
+        for i in 0..MAX_DECK_SIZE {
+            if !is_padding[i] {
+                deck[deck_size] = padded_deck[i];
+                deck_size += 1;
+            }
+        }
 
-        // Encode the shuffled deck
-        let encrypted_deck = mxe.from_arcis(Deck::from_array(deck));
+        // Encode the shuffled deck and commit to its order
+        let packed_deck = Deck::from_array(deck);
+        let salt = ArcisRNG::gen_integer_from_width(128);
+        let commitment = deck_commitment(&packed_deck, salt);
+        let mut revealed_commitment = [0u8; 32];
+        for i in 0..32 {
+            revealed_commitment[i] = commitment[i].reveal();
+        }
+        let encrypted_deck = mxe.from_arcis(packed_deck);
 
         // Deal hole cards to player
         let mut hole_cards = [53; 11]; // 53 = empty card marker
+        let mut cards_dealt = 0;
         for i in 0..11 {
-            if i < num_hole_cards {
+            if i < num_hole_cards && (i as usize) < deck_size {
                 hole_cards[i as usize] = deck[i as usize];
+                cards_dealt += 1;
             }
         }
 
         let encrypted_hole_cards = client.from_arcis(Hand::from_array(hole_cards));
 
-        (encrypted_deck, encrypted_hole_cards, num_hole_cards)
+        (
+            encrypted_deck,
+            encrypted_hole_cards,
+            cards_dealt,
+            salt_mxe.from_arcis(salt),
+            revealed_commitment,
+        )
     }
 
     /// Stores encrypted hole cards for a player.
-    /// Takes an existing hand and adds new cards from the deck.
+    /// Takes an existing hand and adds new cards from the deck, after burning
+    /// `num_burn` cards. Burned cards stay encrypted in the deck.
     ///
     /// Returns:
     /// - Updated encrypted hand
     /// - Number of cards now in hand
+    /// - Seat the hand belongs to
+    /// - Number of cards burned
     #[instruction]
     pub fn store_hole_cards(
         deck_ctxt: Enc<Mxe, Deck>,
         existing_hand_ctxt: Enc<Shared, Hand>,
         existing_hand_size: u8,
         num_new_cards: u8,
-        cards_already_dealt: u8,
-    ) -> (Enc<Shared, Hand>, u8) {
+        num_burn: u8,
+        cards_already_dealt: u16,
+        deck_size: u16,
+        seat: u8,
+    ) -> (Enc<Shared, Hand>, u8, u8, u8) {
         let deck = deck_ctxt.to_arcis().to_array();
         let mut hand = existing_hand_ctxt.to_arcis().to_array();
 
@@ -188,8 +840,8 @@ mod circuits {
         let mut cards_added = 0;
         for i in 0..11 {
             if i < num_new_cards && existing_hand_size + i < 11 {
-                let deck_index = (cards_already_dealt + i) as usize;
-                if deck_index < 52 {
+                let deck_index = cards_already_dealt as usize + num_burn as usize + i as usize;
+                if deck_index < deck_size as usize {
                     hand[(existing_hand_size + i) as usize] = deck[deck_index];
                     cards_added += 1;
                 }
@@ -200,21 +852,24 @@ mod circuits {
             .owner
             .from_arcis(Hand::from_array(hand));
 
-        (updated_hand, existing_hand_size + cards_added)
+        (updated_hand, existing_hand_size + cards_added, seat, num_burn)
     }
 
-    /// Reveals N community cards from the deck
-    /// These cards are revealed as plaintext
+    /// Reveals N community cards from the deck, after burning `num_burn` cards
+    /// These cards are revealed as plaintext; burned cards stay encrypted
     ///
     /// Returns:
     /// - Array of revealed community cards (up to 5 cards for poker)
     /// - Number of cards revealed
+    /// - Number of cards burned
     #[instruction]
     pub fn reveal_community_cards(
         deck_ctxt: Enc<Mxe, Deck>,
         num_cards_to_reveal: u8,
-        cards_already_dealt: u8,
-    ) -> ([u8; 5], u8) {
+        num_burn: u8,
+        cards_already_dealt: u16,
+        deck_size: u16,
+    ) -> ([u8; 5], u8, u8) {
         let deck = deck_ctxt.to_arcis().to_array();
 
         let mut community_cards = [53u8; 5]; 
@@ -222,8 +877,8 @@ mod circuits {
 
         for i in 0..5 {
             if i < num_cards_to_reveal {
-                let deck_index = (cards_already_dealt + i) as usize;
-                if deck_index < 52 {
+                let deck_index = cards_already_dealt as usize + num_burn as usize + i as usize;
+                if deck_index < deck_size as usize {
                     community_cards[i as usize] = deck[deck_index];
                     cards_revealed += 1;
                 }
@@ -239,7 +894,42 @@ mod circuits {
             community_cards[4].reveal(),
         ];
 
-        (revealed_cards, cards_revealed.reveal())
+        (revealed_cards, cards_revealed.reveal(), num_burn)
+    }
+
+    /// Reveals a seat's hole cards as plaintext, e.g. for a showdown.
+    /// Only the slots whose bit is set in `reveal_mask` are revealed, so a
+    /// player can show part of a hand and keep the rest hidden.
+    ///
+    /// Returns:
+    /// - Revealed cards packed to the front (empty card marker elsewhere)
+    /// - Number of cards revealed
+    /// - Seat the hand belongs to
+    #[instruction]
+    pub fn reveal_hole_cards(
+        hand_ctxt: Enc<Shared, Hand>,
+        hand_size: u8,
+        reveal_mask: u16,
+        seat: u8,
+    ) -> ([u8; 11], u8, u8) {
+        let hand = hand_ctxt.to_arcis().to_array();
+
+        let mut selected_cards = [53u8; 11];
+        let mut cards_revealed = 0;
+        for i in 0..11 {
+            if i < hand_size && ((reveal_mask >> i) & 1) == 1 {
+                selected_cards[cards_revealed as usize] = hand[i as usize];
+                cards_revealed += 1;
+            }
+        }
+
+        // Reveal the selected cards (make them public)
+        let mut revealed_cards = [53u8; 11];
+        for i in 0..11 {
+            revealed_cards[i] = selected_cards[i].reveal();
+        }
+
+        (revealed_cards, cards_revealed, seat)
     }
 
     /// Changes/resets a hand for a new round
@@ -247,9 +937,685 @@ mod circuits {
     ///
     /// Returns:
     /// - New empty encrypted hand
+    /// - Seat the hand belongs to
     #[instruction]
-    pub fn change_hand(client: Shared) -> Enc<Shared, Hand> {
+    pub fn change_hand(client: Shared, seat: u8) -> (Enc<Shared, Hand>, u8) {
         let empty_hand = [53; 11]; // All cards set to empty marker
-        client.from_arcis(Hand::from_array(empty_hand))
+        (client.from_arcis(Hand::from_array(empty_hand)), seat)
+    }
+
+    /// Deals hole cards from an already shuffled deck to a newly seated player.
+    /// Every seat draws from the same deck, so no two players can hold the same card.
+    ///
+    /// Returns:
+    /// - Encrypted hole cards for the seat (shared encryption with the player)
+    /// - Number of cards dealt
+    /// - Seat the hand belongs to
+    #[instruction]
+    pub fn seat_player(
+        deck_ctxt: Enc<Mxe, Deck>,
+        client: Shared,
+        num_hole_cards: u8,
+        cards_already_dealt: u16,
+        deck_size: u16,
+        seat: u8,
+    ) -> (Enc<Shared, Hand>, u8, u8) {
+        let deck = deck_ctxt.to_arcis().to_array();
+
+        let mut hole_cards = [53; 11]; // 53 = empty card marker
+        let mut cards_dealt = 0;
+        for i in 0..11 {
+            if i < num_hole_cards {
+                let deck_index = cards_already_dealt as usize + i as usize;
+                if deck_index < deck_size as usize {
+                    hole_cards[i as usize] = deck[deck_index];
+                    cards_dealt += 1;
+                }
+            }
+        }
+
+        let encrypted_hole_cards = client.from_arcis(Hand::from_array(hole_cards));
+
+        (encrypted_hole_cards, cards_dealt, seat)
+    }
+
+    /// Resolves a hold'em showdown without revealing any hand.
+    /// Each contending seat's best five-card hand is built from its first two
+    /// hole cards and the five community cards, and only the winner(s) and the
+    /// winning hand category are made public. Seats outside `contenders`
+    /// (folded or empty) are ignored.
+    ///
+    /// Returns:
+    /// - Bitmask of winning seats (more than one bit set on a split pot)
+    /// - Category of the winning hand
+    #[instruction]
+    pub fn resolve_showdown(
+        seat_0: Enc<Shared, Hand>,
+        seat_1: Enc<Shared, Hand>,
+        seat_2: Enc<Shared, Hand>,
+        seat_3: Enc<Shared, Hand>,
+        seat_4: Enc<Shared, Hand>,
+        seat_5: Enc<Shared, Hand>,
+        seat_6: Enc<Shared, Hand>,
+        seat_7: Enc<Shared, Hand>,
+        seat_8: Enc<Shared, Hand>,
+        seat_9: Enc<Shared, Hand>,
+        community_cards: [u8; 5],
+        contenders: u16,
+    ) -> (u16, u8) {
+        let hands = [
+            seat_0.to_arcis().to_array(),
+            seat_1.to_arcis().to_array(),
+            seat_2.to_arcis().to_array(),
+            seat_3.to_arcis().to_array(),
+            seat_4.to_arcis().to_array(),
+            seat_5.to_arcis().to_array(),
+            seat_6.to_arcis().to_array(),
+            seat_7.to_arcis().to_array(),
+            seat_8.to_arcis().to_array(),
+            seat_9.to_arcis().to_array(),
+        ];
+
+        let mut scores = [0u32; 10];
+        for seat in 0..10 {
+            let cards = [
+                hands[seat][0],
+                hands[seat][1],
+                community_cards[0],
+                community_cards[1],
+                community_cards[2],
+                community_cards[3],
+                community_cards[4],
+            ];
+            scores[seat] = poker_hand_score(cards);
+        }
+
+        let mut best_score = 0;
+        for seat in 0..10 {
+            if ((contenders >> seat) & 1) == 1 && scores[seat] > best_score {
+                best_score = scores[seat];
+            }
+        }
+
+        let mut winners: u16 = 0;
+        for seat in 0..10 {
+            if ((contenders >> seat) & 1) == 1 && scores[seat] == best_score {
+                winners += 1 << seat;
+            }
+        }
+
+        let category = (best_score >> HAND_CATEGORY_SHIFT) as u8;
+
+        (winners.reveal(), category.reveal())
+    }
+
+    /// Deals the blackjack dealer two cards encrypted to the MXE only, so no
+    /// player (or the house) can see the hole card. The first card is the upcard.
+    ///
+    /// Returns:
+    /// - Encrypted dealer hand
+    /// - The dealer's upcard (revealed)
+    #[instruction]
+    pub fn deal_dealer_hand(
+        mxe: Mxe,
+        deck_ctxt: Enc<Mxe, Deck>,
+        cards_already_dealt: u16,
+        deck_size: u16,
+    ) -> (Enc<Mxe, Hand>, u8) {
+        let deck = deck_ctxt.to_arcis().to_array();
+
+        let mut dealer_hand = [53; 11];
+        for i in 0..2 {
+            let deck_index = cards_already_dealt as usize + i;
+            if deck_index < deck_size as usize {
+                dealer_hand[i] = deck[deck_index];
+            }
+        }
+
+        let upcard = dealer_hand[0];
+
+        (mxe.from_arcis(Hand::from_array(dealer_hand)), upcard.reveal())
+    }
+
+    /// Draws one card from the deck into a blackjack player's hand.
+    /// The new total stays hidden; only whether the hand busted is revealed.
+    ///
+    /// Returns:
+    /// - Updated encrypted hand
+    /// - Number of cards now in hand
+    /// - Whether the hand is now over 21
+    /// - Seat the hand belongs to
+    #[instruction]
+    pub fn player_hit(
+        deck_ctxt: Enc<Mxe, Deck>,
+        hand_ctxt: Enc<Shared, Hand>,
+        hand_size: u8,
+        cards_already_dealt: u16,
+        deck_size: u16,
+        seat: u8,
+    ) -> (Enc<Shared, Hand>, u8, bool, u8) {
+        let deck = deck_ctxt.to_arcis().to_array();
+        let mut hand = hand_ctxt.to_arcis().to_array();
+
+        let mut new_hand_size = hand_size;
+        let deck_index = cards_already_dealt as usize;
+        if hand_size < 11 && deck_index < deck_size as usize {
+            hand[hand_size as usize] = deck[deck_index];
+            new_hand_size += 1;
+        }
+
+        let (total, _) = blackjack_total(hand);
+        let busted = total > 21;
+
+        let updated_hand = hand_ctxt.owner.from_arcis(Hand::from_array(hand));
+
+        (updated_hand, new_hand_size, busted.reveal(), seat)
+    }
+
+    /// Plays out the dealer's hand under hit-on-soft-17 rules and settles every
+    /// contending seat against it. The dealer's hand and each outcome are revealed;
+    /// player totals are not.
+    ///
+    /// Outcomes per seat: 0 not playing, 1 lose, 2 push, 3 win, 4 blackjack
+    ///
+    /// Returns:
+    /// - Dealer's final cards
+    /// - Dealer's final total
+    /// - Number of cards the dealer drew
+    /// - Outcome for each seat
+    #[instruction]
+    pub fn dealer_play(
+        deck_ctxt: Enc<Mxe, Deck>,
+        dealer_ctxt: Enc<Mxe, Hand>,
+        cards_already_dealt: u16,
+        deck_size: u16,
+        seat_0: Enc<Shared, Hand>,
+        seat_1: Enc<Shared, Hand>,
+        seat_2: Enc<Shared, Hand>,
+        seat_3: Enc<Shared, Hand>,
+        seat_4: Enc<Shared, Hand>,
+        seat_5: Enc<Shared, Hand>,
+        seat_6: Enc<Shared, Hand>,
+        seat_7: Enc<Shared, Hand>,
+        seat_8: Enc<Shared, Hand>,
+        seat_9: Enc<Shared, Hand>,
+        hand_sizes: [u8; 10],
+        contenders: u16,
+    ) -> ([u8; 11], u8, u8, [u8; 10]) {
+        let deck = deck_ctxt.to_arcis().to_array();
+        let mut dealer_hand = dealer_ctxt.to_arcis().to_array();
+
+        let (initial_total, _) = blackjack_total(dealer_hand);
+        let dealer_natural = initial_total == 21;
+
+        // The i-th draw always comes from the i-th undealt card; only whether
+        // the dealer takes it depends on the (hidden) running total
+        let mut cards_drawn = 0;
+        for i in 0..9 {
+            let (total, soft) = blackjack_total(dealer_hand);
+            let hits = total < 17 || (total == 17 && soft);
+            let deck_index = cards_already_dealt as usize + i;
+            if hits && deck_index < deck_size as usize {
+                dealer_hand[2 + i] = deck[deck_index];
+                cards_drawn += 1;
+            }
+        }
+        let (dealer_total, _) = blackjack_total(dealer_hand);
+        let dealer_busted = dealer_total > 21;
+
+        let hands = [
+            seat_0.to_arcis().to_array(),
+            seat_1.to_arcis().to_array(),
+            seat_2.to_arcis().to_array(),
+            seat_3.to_arcis().to_array(),
+            seat_4.to_arcis().to_array(),
+            seat_5.to_arcis().to_array(),
+            seat_6.to_arcis().to_array(),
+            seat_7.to_arcis().to_array(),
+            seat_8.to_arcis().to_array(),
+            seat_9.to_arcis().to_array(),
+        ];
+
+        let mut outcomes = [0u8; 10];
+        for seat in 0..10 {
+            let (player_total, _) = blackjack_total(hands[seat]);
+            let player_natural = hand_sizes[seat] == 2 && player_total == 21;
+
+            let mut outcome = 1;
+            if player_total <= 21 {
+                if player_natural && !dealer_natural {
+                    outcome = 4;
+                } else if player_natural && dealer_natural {
+                    outcome = 2;
+                } else if !dealer_natural {
+                    if dealer_busted || player_total > dealer_total {
+                        outcome = 3;
+                    } else if player_total == dealer_total {
+                        outcome = 2;
+                    }
+                }
+            }
+
+            if ((contenders >> seat) & 1) == 1 {
+                outcomes[seat] = outcome;
+            }
+        }
+
+        let mut revealed_dealer_hand = [53u8; 11];
+        for i in 0..11 {
+            revealed_dealer_hand[i] = dealer_hand[i].reveal();
+        }
+        let mut revealed_outcomes = [0u8; 10];
+        for seat in 0..10 {
+            revealed_outcomes[seat] = outcomes[seat].reveal();
+        }
+
+        (
+            revealed_dealer_hand,
+            dealer_total.reveal(),
+            cards_drawn.reveal(),
+            revealed_outcomes,
+        )
+    }
+
+    /// Reshuffles the cards not yet dealt, leaving dealt positions (and so the
+    /// cards already in hands or on the board) untouched.
+    ///
+    /// Player seeds are mixed in as in `shuffle_and_deal_deck`.
+    ///
+    /// The deck commitment is replaced with one to the new order, under a fresh
+    /// salt, chained to `previous_commitment` (see `chained_deck_commitment`).
+    ///
+    /// Returns:
+    /// - Encrypted deck with positions `cards_already_dealt..deck_size` reshuffled
+    /// - Encrypted commitment salt
+    /// - Deck commitment
+    #[instruction]
+    pub fn reshuffle_remaining(
+        deck_ctxt: Enc<Mxe, Deck>,
+        salt_ctxt: Enc<Mxe, u128>,
+        cards_already_dealt: u16,
+        deck_size: u16,
+        previous_commitment: [u8; 32],
+        num_seeds: u8,
+        seed_0: Enc<Shared, u128>,
+        seed_1: Enc<Shared, u128>,
+        seed_2: Enc<Shared, u128>,
+        seed_3: Enc<Shared, u128>,
+    ) -> (Enc<Mxe, Deck>, Enc<Mxe, u128>, [u8; 32]) {
+        let mut deck = deck_ctxt.to_arcis().to_array();
+
+        // Pull the undealt cards out, padding the rest so the shuffle runs over a
+        // fixed number of slots
+        let mut remaining = [DECK_PADDING; MAX_DECK_SIZE];
+        for i in 0..MAX_DECK_SIZE {
+            let deck_index = cards_already_dealt as usize + i;
+            if deck_index < deck_size as usize {
+                remaining[i] = deck[deck_index];
+            }
+        }
+        let seeds = [
+            seed_0.to_arcis(),
+            seed_1.to_arcis(),
+            seed_2.to_arcis(),
+            seed_3.to_arcis(),
+        ];
+        keyed_shuffle(&mut remaining, seed_entropy(num_seeds, seeds));
+
+        // Write them back behind the dealt cards, skipping the padding as
+        // shuffle_and_deal_deck does
+        let mut is_padding = [false; MAX_DECK_SIZE];
+        for i in 0..MAX_DECK_SIZE {
+            is_padding[i] = (remaining[i] == DECK_PADDING).reveal();
+        }
+        let mut deck_index = cards_already_dealt as usize;
+        for i in 0..MAX_DECK_SIZE {
+            if !is_padding[i] && deck_index < MAX_DECK_SIZE {
+                deck[deck_index] = remaining[i];
+                deck_index += 1;
+            }
+        }
+
+        let packed_deck = Deck::from_array(deck);
+        let salt = ArcisRNG::gen_integer_from_width(128);
+        let commitment = chained_deck_commitment(previous_commitment, &packed_deck, salt);
+        let mut revealed_commitment = [0u8; 32];
+        for i in 0..32 {
+            revealed_commitment[i] = commitment[i].reveal();
+        }
+
+        (
+            deck_ctxt.owner.from_arcis(packed_deck),
+            salt_ctxt.owner.from_arcis(salt),
+            revealed_commitment,
+        )
+    }
+
+    /// Discards the cards at the positions set in `discard_mask` (bit i = hand
+    /// position i) and replaces each from the deck, keeping the rest of the hand
+    /// in place. The discards are appended to the MXE-encrypted discard pile.
+    ///
+    /// Returns:
+    /// - Updated encrypted hand
+    /// - Updated encrypted discard pile
+    /// - Number of cards discarded (and drawn)
+    /// - Seat the hand belongs to
+    #[instruction]
+    pub fn discard_and_draw(
+        deck_ctxt: Enc<Mxe, Deck>,
+        hand_ctxt: Enc<Shared, Hand>,
+        discard_pile_ctxt: Enc<Mxe, Deck>,
+        hand_size: u8,
+        discard_mask: u16,
+        discard_pile_size: u16,
+        cards_already_dealt: u16,
+        deck_size: u16,
+        seat: u8,
+    ) -> (Enc<Shared, Hand>, Enc<Mxe, Deck>, u8, u8) {
+        let deck = deck_ctxt.to_arcis().to_array();
+        let mut hand = hand_ctxt.to_arcis().to_array();
+        let mut discard_pile = discard_pile_ctxt.to_arcis().to_array();
+
+        let mut cards_drawn = 0;
+        for i in 0..11 {
+            if (i as u8) < hand_size && ((discard_mask >> i) & 1) == 1 {
+                let pile_index = discard_pile_size as usize + cards_drawn as usize;
+                if pile_index < MAX_DECK_SIZE {
+                    discard_pile[pile_index] = hand[i];
+                }
+
+                let deck_index = cards_already_dealt as usize + cards_drawn as usize;
+                hand[i] = if deck_index < deck_size as usize {
+                    deck[deck_index]
+                } else {
+                    53 // 53 = empty card marker
+                };
+                cards_drawn += 1;
+            }
+        }
+
+        (
+            hand_ctxt.owner.from_arcis(Hand::from_array(hand)),
+            discard_pile_ctxt.owner.from_arcis(Deck::from_array(discard_pile)),
+            cards_drawn,
+            seat,
+        )
+    }
+
+    /// Passes cards from one seat's hand to another's without revealing them.
+    /// The sender's encrypted `selection_ctxt` picks the slots to pass (bit i
+    /// for slot i); those cards are appended to the recipient's hand, encrypted
+    /// to the recipient's key, and the sender's remaining cards move up to fill
+    /// the gaps. Nothing moves unless exactly `num_cards` held cards are selected.
+    ///
+    /// Returns:
+    /// - Updated encrypted sender hand
+    /// - Updated encrypted recipient hand
+    /// - Number of cards passed (`num_cards`, or 0 for an invalid selection)
+    /// - Seat the cards were passed from
+    /// - Seat the cards were passed to
+    #[instruction]
+    pub fn pass_cards(
+        sender_hand_ctxt: Enc<Shared, Hand>,
+        recipient_hand_ctxt: Enc<Shared, Hand>,
+        selection_ctxt: Enc<Shared, u16>,
+        sender_hand_size: u8,
+        recipient_hand_size: u8,
+        num_cards: u8,
+        sender_seat: u8,
+        recipient_seat: u8,
+    ) -> (Enc<Shared, Hand>, Enc<Shared, Hand>, u8, u8, u8) {
+        let sender_hand = sender_hand_ctxt.to_arcis().to_array();
+        let existing_recipient_hand = recipient_hand_ctxt.to_arcis().to_array();
+        let selection = selection_ctxt.to_arcis();
+
+        let mut num_selected = 0u8;
+        for i in 0..11 {
+            if (i as u8) < sender_hand_size && ((selection >> i) & 1) == 1 {
+                num_selected += 1;
+            }
+        }
+        let valid_selection = num_selected == num_cards;
+
+        // Keep the cards the recipient already holds
+        let mut recipient_hand = [53u8; 11]; // 53 = empty card marker
+        for i in 0..11 {
+            if (i as u8) < recipient_hand_size {
+                recipient_hand[i] = existing_recipient_hand[i];
+            }
+        }
+
+        // Compact the kept cards to the front of the sender's hand and append
+        // the passed ones to the recipient's
+        let mut kept_hand = [53u8; 11];
+        let mut cards_kept = 0u8;
+        let mut cards_passed = 0u8;
+        for i in 0..11 {
+            let held = (i as u8) < sender_hand_size;
+            let passed = valid_selection && held && ((selection >> i) & 1) == 1;
+            let kept = held && !passed;
+            for j in 0..11 {
+                if kept && cards_kept == j as u8 {
+                    kept_hand[j] = sender_hand[i];
+                }
+                if passed && recipient_hand_size + cards_passed == j as u8 {
+                    recipient_hand[j] = sender_hand[i];
+                }
+            }
+            if kept {
+                cards_kept += 1;
+            }
+            if passed {
+                cards_passed += 1;
+            }
+        }
+
+        (
+            sender_hand_ctxt.owner.from_arcis(Hand::from_array(kept_hand)),
+            recipient_hand_ctxt
+                .owner
+                .from_arcis(Hand::from_array(recipient_hand)),
+            cards_passed.reveal(),
+            sender_seat,
+            recipient_seat,
+        )
+    }
+
+    /// Publishes the deck and the commitment salt so the game can be audited
+    /// against the commitment made when the deck was last shuffled.
+    ///
+    /// Returns:
+    /// - Packed deck limbs (plaintext)
+    /// - Commitment salt (plaintext)
+    #[instruction]
+    pub fn reveal_deck(
+        deck_ctxt: Enc<Mxe, Deck>,
+        salt_ctxt: Enc<Mxe, u128>,
+    ) -> ([u128; DECK_LIMBS], u128) {
+        let deck = deck_ctxt.to_arcis();
+        let mut revealed_deck = [0u128; DECK_LIMBS];
+        for i in 0..DECK_LIMBS {
+            revealed_deck[i] = deck.cards[i].reveal();
+        }
+
+        (revealed_deck, salt_ctxt.to_arcis().reveal())
+    }
+
+    /// Plays a baccarat coup from the shoe. Player and Banker are dealt two cards
+    /// each (alternately, Player first), then third cards are drawn by the
+    /// standard tableau before anything is revealed, so no one can influence a
+    /// draw after seeing a card. Both hands and the result are revealed together.
+    ///
+    /// Returns:
+    /// - Player's cards (empty card marker past the hand size)
+    /// - Number of Player cards
+    /// - Banker's cards (empty card marker past the hand size)
+    /// - Number of Banker cards
+    /// - Player total
+    /// - Banker total
+    /// - Outcome (1 Player wins, 2 Banker wins, 3 tie)
+    #[instruction]
+    pub fn play_baccarat(
+        deck_ctxt: Enc<Mxe, Deck>,
+        cards_already_dealt: u16,
+        deck_size: u16,
+    ) -> ([u8; 3], u8, [u8; 3], u8, u8, u8, u8) {
+        let deck = deck_ctxt.to_arcis().to_array();
+
+        // A coup uses at most six cards
+        let mut next_cards = [53u8; 6];
+        for i in 0..6 {
+            let deck_index = cards_already_dealt as usize + i;
+            if deck_index < deck_size as usize {
+                next_cards[i] = deck[deck_index];
+            }
+        }
+
+        let mut player = [next_cards[0], next_cards[2], 53];
+        let mut banker = [next_cards[1], next_cards[3], 53];
+        let mut player_total = baccarat_add(baccarat_card_value(player[0]), player[1]);
+        let mut banker_total = baccarat_add(baccarat_card_value(banker[0]), banker[1]);
+
+        // A natural 8 or 9 on either side ends the coup
+        let natural = player_total >= 8 || banker_total >= 8;
+
+        // Player draws on 0-5
+        let player_draws = !natural && player_total <= 5;
+        let player_third = next_cards[4];
+        let third_value = baccarat_card_value(player_third);
+        if player_draws {
+            player[2] = player_third;
+            player_total = baccarat_add(player_total, player_third);
+        }
+
+        // Banker draws on 0-5 if Player stood, otherwise by the tableau
+        let banker_draws_after_player = banker_total <= 2
+            || (banker_total == 3 && third_value != 8)
+            || (banker_total == 4 && third_value >= 2 && third_value <= 7)
+            || (banker_total == 5 && third_value >= 4 && third_value <= 7)
+            || (banker_total == 6 && third_value >= 6 && third_value <= 7);
+        let banker_draws = !natural
+            && if player_draws {
+                banker_draws_after_player
+            } else {
+                banker_total <= 5
+            };
+        let banker_third = if player_draws {
+            next_cards[5]
+        } else {
+            next_cards[4]
+        };
+        if banker_draws {
+            banker[2] = banker_third;
+            banker_total = baccarat_add(banker_total, banker_third);
+        }
+
+        let outcome: u8 = if player_total > banker_total {
+            1
+        } else if banker_total > player_total {
+            2
+        } else {
+            3
+        };
+        let player_size: u8 = if player_draws { 3 } else { 2 };
+        let banker_size: u8 = if banker_draws { 3 } else { 2 };
+
+        let mut player_cards = [53u8; 3];
+        let mut banker_cards = [53u8; 3];
+        for i in 0..3 {
+            player_cards[i] = player[i].reveal();
+            banker_cards[i] = banker[i].reveal();
+        }
+
+        (
+            player_cards,
+            player_size.reveal(),
+            banker_cards,
+            banker_size.reveal(),
+            player_total.reveal(),
+            banker_total.reveal(),
+            outcome.reveal(),
+        )
+    }
+
+    /// Deals `num_new_cards` to the house seat after burning `num_burn` cards.
+    /// The hand is encrypted to the house key under the fresh nonce in `house`,
+    /// so the house can read it but players cannot. When the house hand is empty
+    /// the stored ciphertext is ignored.
+    ///
+    /// Returns:
+    /// - Updated encrypted house hand
+    /// - Number of cards now in the hand
+    /// - Number of cards burned
+    #[instruction]
+    pub fn deal_house_cards(
+        house: Shared,
+        deck_ctxt: Enc<Mxe, Deck>,
+        house_hand_ctxt: Enc<Shared, Hand>,
+        house_hand_size: u8,
+        num_new_cards: u8,
+        num_burn: u8,
+        cards_already_dealt: u16,
+        deck_size: u16,
+    ) -> (Enc<Shared, Hand>, u8, u8) {
+        let deck = deck_ctxt.to_arcis().to_array();
+        let existing_hand = house_hand_ctxt.to_arcis().to_array();
+
+        // Keep the cards already held
+        let mut hand = [53; 11]; // 53 = empty card marker
+        for i in 0..11 {
+            if i < house_hand_size {
+                hand[i as usize] = existing_hand[i as usize];
+            }
+        }
+
+        // Add new cards to the hand
+        let mut cards_added = 0;
+        for i in 0..11 {
+            if i < num_new_cards && house_hand_size + i < 11 {
+                let deck_index = cards_already_dealt as usize + num_burn as usize + i as usize;
+                if deck_index < deck_size as usize {
+                    hand[(house_hand_size + i) as usize] = deck[deck_index];
+                    cards_added += 1;
+                }
+            }
+        }
+
+        (
+            house.from_arcis(Hand::from_array(hand)),
+            house_hand_size + cards_added,
+            num_burn,
+        )
+    }
+
+    /// Reveals the house hand as plaintext. Only the slots whose bit is set in
+    /// `reveal_mask` are revealed, e.g. a blackjack dealer's upcard alone.
+    ///
+    /// Returns:
+    /// - Revealed cards packed to the front (empty card marker elsewhere)
+    /// - Number of cards revealed
+    #[instruction]
+    pub fn reveal_house_cards(
+        house_hand_ctxt: Enc<Shared, Hand>,
+        hand_size: u8,
+        reveal_mask: u16,
+    ) -> ([u8; 11], u8) {
+        let hand = house_hand_ctxt.to_arcis().to_array();
+
+        let mut selected_cards = [53u8; 11];
+        let mut cards_revealed = 0;
+        for i in 0..11 {
+            if i < hand_size && ((reveal_mask >> i) & 1) == 1 {
+                selected_cards[cards_revealed as usize] = hand[i as usize];
+                cards_revealed += 1;
+            }
+        }
+
+        let mut revealed_cards = [53u8; 11];
+        for i in 0..11 {
+            revealed_cards[i] = selected_cards[i].reveal();
+        }
+
+        (revealed_cards, cards_revealed)
     }
 }
@@ -1,11 +1,67 @@
 use anchor_lang::prelude::*;
 use arcium_anchor::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
 use arcium_client::idl::arcium::types::CallbackAccount;
 
 const COMP_DEF_OFFSET_SHUFFLE_AND_DEAL: u32 = comp_def_offset("shuffle_and_deal_deck");
 const COMP_DEF_OFFSET_STORE_HOLE_CARDS: u32 = comp_def_offset("store_hole_cards");
 const COMP_DEF_OFFSET_REVEAL_COMMUNITY: u32 = comp_def_offset("reveal_community_cards");
 const COMP_DEF_OFFSET_CHANGE_HAND: u32 = comp_def_offset("change_hand");
+const COMP_DEF_OFFSET_SEAT_PLAYER: u32 = comp_def_offset("seat_player");
+const COMP_DEF_OFFSET_REVEAL_HOLE_CARDS: u32 = comp_def_offset("reveal_hole_cards");
+const COMP_DEF_OFFSET_RESOLVE_SHOWDOWN: u32 = comp_def_offset("resolve_showdown");
+const COMP_DEF_OFFSET_DEAL_DEALER_HAND: u32 = comp_def_offset("deal_dealer_hand");
+const COMP_DEF_OFFSET_PLAYER_HIT: u32 = comp_def_offset("player_hit");
+const COMP_DEF_OFFSET_DEALER_PLAY: u32 = comp_def_offset("dealer_play");
+const COMP_DEF_OFFSET_RESHUFFLE_REMAINING: u32 = comp_def_offset("reshuffle_remaining");
+const COMP_DEF_OFFSET_DISCARD_AND_DRAW: u32 = comp_def_offset("discard_and_draw");
+const COMP_DEF_OFFSET_REVEAL_DECK: u32 = comp_def_offset("reveal_deck");
+const COMP_DEF_OFFSET_DEAL_HOUSE_CARDS: u32 = comp_def_offset("deal_house_cards");
+const COMP_DEF_OFFSET_REVEAL_HOUSE_CARDS: u32 = comp_def_offset("reveal_house_cards");
+const COMP_DEF_OFFSET_PLAY_BACCARAT: u32 = comp_def_offset("play_baccarat");
+const COMP_DEF_OFFSET_PASS_CARDS: u32 = comp_def_offset("pass_cards");
+
+/// Maximum number of seats (players) sharing a single deck
+pub const MAX_SEATS: usize = 10;
+/// Maximum number of cards a single hand can hold
+pub const MAX_HOLE_CARDS: usize = 11;
+/// Maximum number of community cards on the board
+pub const MAX_COMMUNITY_CARDS: usize = 5;
+/// Maximum number of copies of the base deck in a shoe
+pub const MAX_DECK_COPIES: u8 = 6;
+/// Number of u128 limbs holding the packed deck (21 cards per limb, up to 324 cards)
+pub const DECK_LIMBS: usize = 16;
+/// Maximum number of player seeds mixed into a single shuffle
+pub const MAX_ENTROPY_SEEDS: usize = 4;
+/// Most cards a baccarat coup uses (three each for Player and Banker)
+const MAX_BACCARAT_CARDS: u16 = 6;
+/// Most cards the blackjack dealer can draw onto their first two
+const MAX_DEALER_DRAWS: u16 = MAX_HOLE_CARDS as u16 - 2;
+/// Slots after queueing before the authority may cancel a computation whose
+/// callback has not landed (about ten minutes at 400 ms slots)
+pub const COMPUTATION_TIMEOUT_SLOTS: u64 = 1_500;
+
+/// Byte offset of the encrypted deck within the CardGame account (after the discriminator)
+const DECK_OFFSET: u32 = 8;
+/// Byte length of the encrypted deck ciphertexts
+const DECK_LEN: u32 = 32 * DECK_LIMBS as u32;
+/// Byte offset of the dealer's encrypted hand within the CardGame account (after the deck and its nonce)
+const DEALER_HAND_OFFSET: u32 = DECK_OFFSET + DECK_LEN + 16;
+/// Byte offset of the encrypted discard pile within the CardGame account (after the dealer hand and its nonce)
+const DISCARD_PILE_OFFSET: u32 = DEALER_HAND_OFFSET + 32 + 16;
+/// Byte offset of the encrypted deck commitment salt within the CardGame account (after the discard pile and its nonce)
+const DECK_SALT_OFFSET: u32 = DISCARD_PILE_OFFSET + DECK_LEN + 16;
+/// Byte offset of the first seat within the CardGame account (after the deck salt and its nonce)
+const SEATS_OFFSET: u32 = DECK_SALT_OFFSET + 32 + 16;
+/// Byte offset of the house seat's encrypted hand within the CardGame account (right after the seats)
+const HOUSE_HAND_OFFSET: u32 = SEATS_OFFSET + MAX_SEATS as u32 * Seat::INIT_SPACE as u32;
+
+/// Seed of the PDA that signs consumer program notifications
+pub const NOTIFIER_SEED: &[u8] = b"notifier";
+/// Anchor discriminator of the consumer's `on_card_shuffler_callback` instruction
+const CONSUMER_CALLBACK_DISCRIMINATOR: [u8; 8] = [159, 185, 3, 108, 62, 89, 170, 126];
 
 declare_id!("DQxanaqqWcTYvVhrKbeoY6q52NrGksWBL6vSbuVipnS7");
 
@@ -22,38 +78,90 @@ pub mod card_shuffler {
     }
 
     /// Initializes a new card game and shuffles the deck
-    /// Deals initial hole cards to the player encrypted with their public key
+    /// Deals initial hole cards to the creator (seat 0) encrypted with their public key.
+    /// Up to MAX_ENTROPY_SEEDS players can contribute `entropy_seeds` to the shuffle.
+    /// The `authority` signer (the creator, or a game program's PDA) controls the game from then on.
+    /// `salt_nonce` encrypts the salt of the deck commitment and must differ from `mxe_nonce`
     pub fn initialize_card_game(
         ctx: Context<InitializeCardGame>,
         computation_offset: u64,
         game_id: u64,
         mxe_nonce: u128,
+        salt_nonce: u128,
         client_pubkey: [u8; 32],
         client_nonce: u128,
         num_hole_cards: u8,
+        deck_spec: DeckSpec,
+        max_community_cards: u8,
+        entropy_seeds: Vec<EntropySeed>,
     ) -> Result<()> {
+        require!(deck_spec.is_valid(), ErrorCode::InvalidDeckSpec);
+        require!(
+            max_community_cards as usize <= MAX_COMMUNITY_CARDS,
+            ErrorCode::InvalidBoardSize
+        );
+        require!(
+            num_hole_cards as usize <= MAX_HOLE_CARDS,
+            ErrorCode::HandFull
+        );
+        require!(
+            num_hole_cards as u16 <= deck_spec.deck_size(),
+            ErrorCode::DeckExhausted
+        );
+
         let card_game = &mut ctx.accounts.card_game;
         card_game.bump = ctx.bumps.card_game;
         card_game.game_id = game_id;
-        card_game.player_pubkey = ctx.accounts.payer.key();
-        card_game.player_enc_pubkey = client_pubkey;
-        card_game.deck = [[0; 32]; 3];
+        card_game.authority = ctx.accounts.authority.key();
+        card_game.deck = [[0; 32]; DECK_LIMBS];
         card_game.deck_nonce = 0;
-        card_game.hole_cards = [0; 32];
-        card_game.hole_cards_nonce = 0;
-        card_game.hole_cards_size = 0;
+        card_game.dealer_hand = [0; 32];
+        card_game.dealer_hand_nonce = 0;
+        card_game.deck_salt = [0; 32];
+        card_game.deck_salt_nonce = 0;
+        card_game.seats = [Seat::default(); MAX_SEATS];
+        card_game.seats[0].player_pubkey = ctx.accounts.payer.key();
+        card_game.seats[0].player_enc_pubkey = client_pubkey;
+        card_game.num_seats = 1;
+        card_game.house = Seat::default();
         card_game.community_cards = [53; 5];
         card_game.community_cards_size = 0;
+        card_game.max_community_cards = max_community_cards;
         card_game.cards_dealt = 0;
+        card_game.showdown_winners = 0;
+        card_game.showdown_category = 0;
+        card_game.dealer_hand_size = 0;
+        card_game.dealer_upcard = 53;
+        card_game.dealer_cards = [53; 11];
+        card_game.dealer_total = 0;
+        card_game.dealer_played = false;
+        card_game.blackjack_outcomes = [0; MAX_SEATS];
+        card_game.deck_spec = deck_spec;
+        card_game.deck_size = deck_spec.deck_size();
+        card_game.deck_shuffled = false;
+        card_game.deck_commitment = [0; 32];
+        card_game.previous_deck_commitment = None;
+        card_game.deck_revealed = false;
+        card_game.pending_notification = None;
 
         // Queue the shuffle and deal computation
-        let args = vec![
+        let mut args = vec![
             Argument::PlaintextU128(mxe_nonce),
+            Argument::PlaintextU128(salt_nonce),
             Argument::ArcisPubkey(client_pubkey),
             Argument::PlaintextU128(client_nonce),
             Argument::PlaintextU8(num_hole_cards),
+            // Deck composition
+            Argument::PlaintextU16(deck_spec.rank_mask),
+            Argument::PlaintextU8(deck_spec.suit_mask),
+            Argument::PlaintextU8(deck_spec.copies),
+            Argument::PlaintextU8(deck_spec.jokers),
         ];
+        push_entropy_seed_args(&mut args, &entropy_seeds, client_pubkey)?;
 
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::ShuffleAndDeal)?;
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
         queue_computation(
@@ -61,10 +169,11 @@ pub mod card_shuffler {
             computation_offset,
             args,
             None,
-            vec![ShuffleAndDealDeckCallback::callback_ix(&[CallbackAccount {
-                pubkey: ctx.accounts.card_game.key(),
-                is_writable: true,
-            }])],
+            vec![ShuffleAndDealDeckCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
         )?;
 
         Ok(())
@@ -76,6 +185,11 @@ pub mod card_shuffler {
         ctx: Context<ShuffleAndDealDeckCallback>,
         output: ComputationOutputs<ShuffleAndDealDeckOutput>,
     ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::ShuffleAndDeal,
+        )?;
+
         let o = match output {
             ComputationOutputs::Success(ShuffleAndDealDeckOutput {
                 field_0:
@@ -83,35 +197,56 @@ pub mod card_shuffler {
                         field_0: deck,
                         field_1: hole_cards,
                         field_2: num_dealt,
+                        field_3: salt,
+                        field_4: commitment,
                     },
-            }) => (deck, hole_cards, num_dealt),
-            _ => return Err(ErrorCode::AbortedComputation.into()),
+            }) => (deck, hole_cards, num_dealt, salt, commitment),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::ShuffleAndDeal,
+                )
+            }
         };
 
         let deck_nonce = o.0.nonce;
-        let deck: [[u8; 32]; 3] = o.0.ciphertexts;
+        let deck: [[u8; 32]; DECK_LIMBS] = o.0.ciphertexts;
 
         let hole_cards_nonce = o.1.nonce;
         let hole_cards: [u8; 32] = o.1.ciphertexts[0];
 
         let num_dealt: u8 = o.2;
 
+        let deck_commitment: [u8; 32] = o.4;
+
         let card_game = &mut ctx.accounts.card_game;
         card_game.deck = deck;
         card_game.deck_nonce = deck_nonce;
-        card_game.hole_cards = hole_cards;
-        card_game.hole_cards_nonce = hole_cards_nonce;
-        card_game.hole_cards_size = num_dealt;
-        card_game.cards_dealt = num_dealt;
+        card_game.deck_salt = o.3.ciphertexts[0];
+        card_game.deck_salt_nonce = o.3.nonce;
+        card_game.deck_commitment = deck_commitment;
+        card_game.deck_shuffled = true;
+        card_game.seats[0].hole_cards = hole_cards;
+        card_game.seats[0].hole_cards_nonce = hole_cards_nonce;
+        card_game.seats[0].hole_cards_size = num_dealt;
+        card_game.cards_dealt = num_dealt as u16;
 
         emit!(DeckShuffledEvent {
             game_id: card_game.game_id,
+            deck_size: card_game.deck_size,
             hole_cards,
             hole_cards_nonce,
             num_hole_cards: num_dealt,
+            deck_commitment,
         });
 
-        Ok(())
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::ShuffleAndDeal,
+            true,
+        )
     }
 
     /// Initializes the computation definition for storing hole cards
@@ -122,31 +257,51 @@ pub mod card_shuffler {
         Ok(())
     }
 
-    /// Stores additional encrypted hole cards
+    /// Stores additional encrypted hole cards for a seat, optionally burning
+    /// `num_burn` cards from the top of the deck first
     pub fn store_hole_cards(
         ctx: Context<StoreHoleCards>,
         computation_offset: u64,
         _game_id: u64,
+        seat: u8,
         num_new_cards: u8,
+        num_burn: u8,
     ) -> Result<()> {
         let card_game = &ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        card_game.require_can_draw(num_new_cards as u16 + num_burn as u16)?;
+        require!(
+            card_game.seats[seat as usize].hole_cards_size as usize + num_new_cards as usize
+                <= MAX_HOLE_CARDS,
+            ErrorCode::HandFull
+        );
+        let player = &card_game.seats[seat as usize];
 
         let args = vec![
             // Deck
             Argument::PlaintextU128(card_game.deck_nonce),
-            Argument::Account(card_game.key(), 8, 32 * 3),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
             // Existing hand
-            Argument::ArcisPubkey(card_game.player_enc_pubkey),
-            Argument::PlaintextU128(card_game.hole_cards_nonce),
-            Argument::Account(card_game.key(), 8 + 32 * 3, 32),
+            Argument::ArcisPubkey(player.player_enc_pubkey),
+            Argument::PlaintextU128(player.hole_cards_nonce),
+            Argument::Account(card_game.key(), seat_hole_cards_offset(seat), 32),
             // Hand size
-            Argument::PlaintextU8(card_game.hole_cards_size),
+            Argument::PlaintextU8(player.hole_cards_size),
             // New cards to add
             Argument::PlaintextU8(num_new_cards),
+            // Cards to burn first
+            Argument::PlaintextU8(num_burn),
             // Cards already dealt
-            Argument::PlaintextU8(card_game.cards_dealt),
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
+            // Seat the hand belongs to
+            Argument::PlaintextU8(seat),
         ];
 
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::StoreHoleCards)?;
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
         queue_computation(
@@ -154,10 +309,11 @@ pub mod card_shuffler {
             computation_offset,
             args,
             None,
-            vec![StoreHoleCardsCallback::callback_ix(&[CallbackAccount {
-                pubkey: card_game.key(),
-                is_writable: true,
-            }])],
+            vec![StoreHoleCardsCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
         )?;
 
         Ok(())
@@ -169,37 +325,62 @@ pub mod card_shuffler {
         ctx: Context<StoreHoleCardsCallback>,
         output: ComputationOutputs<StoreHoleCardsOutput>,
     ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::StoreHoleCards,
+        )?;
+
         let o = match output {
             ComputationOutputs::Success(StoreHoleCardsOutput {
                 field_0:
                     StoreHoleCardsOutputStruct0 {
                         field_0: updated_hand,
                         field_1: new_hand_size,
+                        field_2: seat,
+                        field_3: num_burned,
                     },
-            }) => (updated_hand, new_hand_size),
-            _ => return Err(ErrorCode::AbortedComputation.into()),
+            }) => (updated_hand, new_hand_size, seat, num_burned),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::StoreHoleCards,
+                )
+            }
         };
 
         let hole_cards_nonce = o.0.nonce;
         let hole_cards: [u8; 32] = o.0.ciphertexts[0];
         let new_size: u8 = o.1;
+        let seat: u8 = o.2;
+        let num_burned: u8 = o.3;
 
         let card_game = &mut ctx.accounts.card_game;
-        let cards_added = new_size - card_game.hole_cards_size;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        let player = &mut card_game.seats[seat as usize];
+        let cards_added = new_size.saturating_sub(player.hole_cards_size);
 
-        card_game.hole_cards = hole_cards;
-        card_game.hole_cards_nonce = hole_cards_nonce;
-        card_game.hole_cards_size = new_size;
-        card_game.cards_dealt += cards_added;
+        player.hole_cards = hole_cards;
+        player.hole_cards_nonce = hole_cards_nonce;
+        player.hole_cards_size = new_size;
+        card_game.cards_dealt += cards_added as u16 + num_burned as u16;
+        card_game.cards_burned += num_burned as u16;
 
         emit!(HoleCardsStoredEvent {
             game_id: card_game.game_id,
+            seat,
             hole_cards,
             hole_cards_nonce,
             total_hole_cards: new_size,
+            num_burned,
         });
 
-        Ok(())
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::StoreHoleCards,
+            true,
+        )
     }
 
     /// Initializes the computation definition for revealing community cards
@@ -210,25 +391,40 @@ pub mod card_shuffler {
         Ok(())
     }
 
-    /// Reveals community cards from the deck
+    /// Reveals community cards from the deck, optionally burning `num_burn`
+    /// cards first (hold'em burns one before the flop, turn and river)
     pub fn reveal_community_cards(
         ctx: Context<RevealCommunityCards>,
         computation_offset: u64,
         _game_id: u64,
         num_cards_to_reveal: u8,
+        num_burn: u8,
     ) -> Result<()> {
         let card_game = &ctx.accounts.card_game;
+        card_game.require_can_draw(num_cards_to_reveal as u16 + num_burn as u16)?;
+        require!(
+            card_game.community_cards_size as usize + num_cards_to_reveal as usize
+                <= card_game.max_community_cards as usize,
+            ErrorCode::RevealLimitExceeded
+        );
 
         let args = vec![
             // Deck
             Argument::PlaintextU128(card_game.deck_nonce),
-            Argument::Account(card_game.key(), 8, 32 * 3),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
             // Number of cards to reveal
             Argument::PlaintextU8(num_cards_to_reveal),
+            // Cards to burn first
+            Argument::PlaintextU8(num_burn),
             // Cards already dealt
-            Argument::PlaintextU8(card_game.cards_dealt),
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
         ];
 
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::RevealCommunityCards)?;
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
         queue_computation(
@@ -236,10 +432,11 @@ pub mod card_shuffler {
             computation_offset,
             args,
             None,
-            vec![RevealCommunityCardsCallback::callback_ix(&[CallbackAccount {
-                pubkey: card_game.key(),
-                is_writable: true,
-            }])],
+            vec![RevealCommunityCardsCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
         )?;
 
         Ok(())
@@ -251,29 +448,71 @@ pub mod card_shuffler {
         ctx: Context<RevealCommunityCardsCallback>,
         output: ComputationOutputs<RevealCommunityCardsOutput>,
     ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::RevealCommunityCards,
+        )?;
+
         let o = match output {
             ComputationOutputs::Success(RevealCommunityCardsOutput {
                 field_0:
                     RevealCommunityCardsOutputStruct0 {
                         field_0: cards,
                         field_1: num,
+                        field_2: num_burned,
                     },
-            }) => (cards, num),
-            _ => return Err(ErrorCode::AbortedComputation.into()),
+            }) => (cards, num, num_burned),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::RevealCommunityCards,
+                )
+            }
         };
 
-        let community_cards = o.0;
+        let new_cards = o.0;
         let num_revealed = o.1;
+        let num_burned: u8 = o.2;
 
+        // Add the new cards to the board rather than replacing it
         let card_game = &mut ctx.accounts.card_game;
-        card_game.community_cards = community_cards;
-        card_game.community_cards_size = num_revealed;
-        card_game.cards_dealt += num_revealed;
+        let board_start = card_game.community_cards_size as usize;
+        let board_end =
+            (board_start + num_revealed as usize).min(card_game.max_community_cards as usize);
+        card_game.community_cards[board_start..board_end]
+            .copy_from_slice(&new_cards[..board_end - board_start]);
+        card_game.community_cards_size = board_end as u8;
+        card_game.cards_dealt += num_revealed as u16 + num_burned as u16;
+        card_game.cards_burned += num_burned as u16;
 
         emit!(CommunityCardsRevealedEvent {
             game_id: card_game.game_id,
-            community_cards,
+            new_cards,
             num_revealed,
+            num_burned,
+            community_cards: card_game.community_cards,
+            community_cards_size: card_game.community_cards_size,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::RevealCommunityCards,
+            true,
+        )
+    }
+
+    /// Clears the community cards so the next hand can deal a new board. The
+    /// cards already revealed stay dealt
+    pub fn clear_board(ctx: Context<ClearBoard>, _game_id: u64) -> Result<()> {
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.require_idle()?;
+        card_game.community_cards = [53; 5];
+        card_game.community_cards_size = 0;
+
+        emit!(BoardClearedEvent {
+            game_id: card_game.game_id,
         });
 
         Ok(())
@@ -285,20 +524,27 @@ pub mod card_shuffler {
         Ok(())
     }
 
-    /// Resets/changes a player's hand for a new round
+    /// Resets/changes a seat's hand for a new round
     pub fn change_hand(
         ctx: Context<ChangeHand>,
         computation_offset: u64,
         _game_id: u64,
+        seat: u8,
         new_nonce: u128,
     ) -> Result<()> {
         let card_game = &ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        card_game.require_idle()?;
 
         let args = vec![
-            Argument::ArcisPubkey(card_game.player_enc_pubkey),
+            Argument::ArcisPubkey(card_game.seats[seat as usize].player_enc_pubkey),
             Argument::PlaintextU128(new_nonce),
+            Argument::PlaintextU8(seat),
         ];
 
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::ChangeHand)?;
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
         queue_computation(
@@ -306,10 +552,11 @@ pub mod card_shuffler {
             computation_offset,
             args,
             None,
-            vec![ChangeHandCallback::callback_ix(&[CallbackAccount {
-                pubkey: card_game.key(),
-                is_writable: true,
-            }])],
+            vec![ChangeHandCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
         )?;
 
         Ok(())
@@ -321,36 +568,3440 @@ pub mod card_shuffler {
         ctx: Context<ChangeHandCallback>,
         output: ComputationOutputs<ChangeHandOutput>,
     ) -> Result<()> {
-        let new_hand = match output {
-            ComputationOutputs::Success(ChangeHandOutput { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::ChangeHand,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(ChangeHandOutput {
+                field_0:
+                    ChangeHandOutputStruct0 {
+                        field_0: new_hand,
+                        field_1: seat,
+                    },
+            }) => (new_hand, seat),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::ChangeHand,
+                )
+            }
         };
 
-        let new_nonce = new_hand.nonce;
-        let new_hand_data: [u8; 32] = new_hand.ciphertexts[0];
+        let new_nonce = o.0.nonce;
+        let new_hand_data: [u8; 32] = o.0.ciphertexts[0];
+        let seat: u8 = o.1;
 
         let card_game = &mut ctx.accounts.card_game;
-        card_game.hole_cards = new_hand_data;
-        card_game.hole_cards_nonce = new_nonce;
-        card_game.hole_cards_size = 0;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        let player = &mut card_game.seats[seat as usize];
+        player.hole_cards = new_hand_data;
+        player.hole_cards_nonce = new_nonce;
+        player.hole_cards_size = 0;
+        player.revealed_cards = [53; 11];
+        player.revealed_cards_size = 0;
+        player.stood = false;
+        player.busted = false;
 
         emit!(HandChangedEvent {
             game_id: card_game.game_id,
+            seat,
             new_hand: new_hand_data,
             new_nonce,
         });
 
-        Ok(())
-    }
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::ChangeHand,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for seating a player
+    pub fn init_seat_player_comp_def(ctx: Context<InitSeatPlayerCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Seats an additional player at the table and deals their hole cards
+    /// from the same shuffled deck, encrypted with the player's own public key
+    pub fn seat_player(
+        ctx: Context<SeatPlayer>,
+        computation_offset: u64,
+        _game_id: u64,
+        player_pubkey: Pubkey,
+        client_pubkey: [u8; 32],
+        client_nonce: u128,
+        num_hole_cards: u8,
+    ) -> Result<()> {
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.require_can_draw(num_hole_cards as u16)?;
+        require!(
+            num_hole_cards as usize <= MAX_HOLE_CARDS,
+            ErrorCode::HandFull
+        );
+        require!(
+            (card_game.num_seats as usize) < MAX_SEATS,
+            ErrorCode::TableFull
+        );
+
+        // Stage the player in the next free seat. It only counts as occupied once
+        // the callback has dealt its cards, so an aborted join leaves no seat
+        // behind; the pending computation keeps other joins off this index
+        let seat = card_game.num_seats;
+        card_game.seats[seat as usize] = Seat {
+            player_pubkey,
+            player_enc_pubkey: client_pubkey,
+            ..Seat::default()
+        };
+
+        let args = vec![
+            // Deck
+            Argument::PlaintextU128(card_game.deck_nonce),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
+            // Player receiving the cards
+            Argument::ArcisPubkey(client_pubkey),
+            Argument::PlaintextU128(client_nonce),
+            // Cards to deal
+            Argument::PlaintextU8(num_hole_cards),
+            // Cards already dealt
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
+            // Seat the hand belongs to
+            Argument::PlaintextU8(seat),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::SeatPlayer)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![SeatPlayerCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "seat_player")]
+    pub fn seat_player_callback(
+        ctx: Context<SeatPlayerCallback>,
+        output: ComputationOutputs<SeatPlayerOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::SeatPlayer,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(SeatPlayerOutput {
+                field_0:
+                    SeatPlayerOutputStruct0 {
+                        field_0: hole_cards,
+                        field_1: num_dealt,
+                        field_2: seat,
+                    },
+            }) => (hole_cards, num_dealt, seat),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::SeatPlayer,
+                )
+            }
+        };
+
+        let hole_cards_nonce = o.0.nonce;
+        let hole_cards: [u8; 32] = o.0.ciphertexts[0];
+        let num_dealt: u8 = o.1;
+        let seat: u8 = o.2;
+
+        let card_game = &mut ctx.accounts.card_game;
+        require!(
+            seat == card_game.num_seats && (seat as usize) < MAX_SEATS,
+            ErrorCode::InvalidSeat
+        );
+        let player = &mut card_game.seats[seat as usize];
+        player.hole_cards = hole_cards;
+        player.hole_cards_nonce = hole_cards_nonce;
+        player.hole_cards_size = num_dealt;
+        card_game.num_seats += 1;
+        card_game.cards_dealt += num_dealt as u16;
+
+        emit!(PlayerSeatedEvent {
+            game_id: card_game.game_id,
+            seat,
+            player_pubkey: card_game.seats[seat as usize].player_pubkey,
+            hole_cards,
+            hole_cards_nonce,
+            num_hole_cards: num_dealt,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::SeatPlayer,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for revealing hole cards
+    pub fn init_reveal_hole_cards_comp_def(
+        ctx: Context<InitRevealHoleCardsCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals a seat's hole cards as plaintext on the CardGame account.
+    /// `reveal_mask` selects which hand slots to reveal (bit i = slot i)
+    pub fn reveal_hole_cards(
+        ctx: Context<RevealHoleCards>,
+        computation_offset: u64,
+        _game_id: u64,
+        seat: u8,
+        reveal_mask: u16,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        card_game.require_idle()?;
+        require!(
+            reveal_mask >> MAX_HOLE_CARDS == 0,
+            ErrorCode::RevealLimitExceeded
+        );
+        let player = &card_game.seats[seat as usize];
+
+        let args = vec![
+            // Hand to reveal
+            Argument::ArcisPubkey(player.player_enc_pubkey),
+            Argument::PlaintextU128(player.hole_cards_nonce),
+            Argument::Account(card_game.key(), seat_hole_cards_offset(seat), 32),
+            // Hand size
+            Argument::PlaintextU8(player.hole_cards_size),
+            // Slots to reveal
+            Argument::PlaintextU16(reveal_mask),
+            // Seat the hand belongs to
+            Argument::PlaintextU8(seat),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::RevealHoleCards)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealHoleCardsCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "reveal_hole_cards")]
+    pub fn reveal_hole_cards_callback(
+        ctx: Context<RevealHoleCardsCallback>,
+        output: ComputationOutputs<RevealHoleCardsOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::RevealHoleCards,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(RevealHoleCardsOutput {
+                field_0:
+                    RevealHoleCardsOutputStruct0 {
+                        field_0: cards,
+                        field_1: num,
+                        field_2: seat,
+                    },
+            }) => (cards, num, seat),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::RevealHoleCards,
+                )
+            }
+        };
+
+        let revealed_cards = o.0;
+        let num_revealed = o.1;
+        let seat: u8 = o.2;
+
+        let card_game = &mut ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        let player = &mut card_game.seats[seat as usize];
+        player.revealed_cards = revealed_cards;
+        player.revealed_cards_size = num_revealed;
+
+        emit!(HoleCardsRevealedEvent {
+            game_id: card_game.game_id,
+            seat,
+            revealed_cards,
+            num_revealed,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::RevealHoleCards,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for resolving a showdown
+    pub fn init_resolve_showdown_comp_def(
+        ctx: Context<InitResolveShowdownCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Determines the winner(s) of a hold'em hand inside MPC.
+    /// `contenders` is a bitmask of the seats still in the hand; every other
+    /// hand stays encrypted, including the losing ones
+    pub fn resolve_showdown(
+        ctx: Context<ResolveShowdown>,
+        computation_offset: u64,
+        _game_id: u64,
+        contenders: u16,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        card_game.require_idle()?;
+        require!(
+            card_game.community_cards_size == 5,
+            ErrorCode::BoardIncomplete
+        );
+        require!(
+            contenders != 0 && contenders >> card_game.num_seats == 0,
+            ErrorCode::InvalidSeat
+        );
+        for seat in 0..card_game.num_seats {
+            if (contenders >> seat) & 1 == 1 {
+                require!(
+                    card_game.seats[seat as usize].hole_cards_size >= 2,
+                    ErrorCode::NotEnoughHoleCards
+                );
+            }
+        }
+
+        let mut args = Vec::with_capacity(MAX_SEATS * 3 + 6);
+        for seat in 0..MAX_SEATS as u8 {
+            // Empty seats reuse seat 0's ciphertext; they are excluded from
+            // `contenders`, so their contents never matter
+            let source = if seat < card_game.num_seats { seat } else { 0 };
+            let player = &card_game.seats[source as usize];
+            args.push(Argument::ArcisPubkey(player.player_enc_pubkey));
+            args.push(Argument::PlaintextU128(player.hole_cards_nonce));
+            args.push(Argument::Account(
+                card_game.key(),
+                seat_hole_cards_offset(source),
+                32,
+            ));
+        }
+        // Board
+        for card in card_game.community_cards {
+            args.push(Argument::PlaintextU8(card));
+        }
+        // Seats still in the hand
+        args.push(Argument::PlaintextU16(contenders));
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::ResolveShowdown)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![ResolveShowdownCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "resolve_showdown")]
+    pub fn resolve_showdown_callback(
+        ctx: Context<ResolveShowdownCallback>,
+        output: ComputationOutputs<ResolveShowdownOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::ResolveShowdown,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(ResolveShowdownOutput {
+                field_0:
+                    ResolveShowdownOutputStruct0 {
+                        field_0: winners,
+                        field_1: category,
+                    },
+            }) => (winners, category),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::ResolveShowdown,
+                )
+            }
+        };
+
+        let winners: u16 = o.0;
+        let hand_category: u8 = o.1;
+
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.showdown_winners = winners;
+        card_game.showdown_category = hand_category;
+
+        emit!(ShowdownResolvedEvent {
+            game_id: card_game.game_id,
+            winners,
+            hand_category,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::ResolveShowdown,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for dealing the blackjack dealer
+    pub fn init_deal_dealer_hand_comp_def(
+        ctx: Context<InitDealDealerHandCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Deals the blackjack dealer two cards from the deck. The hand is
+    /// encrypted to the MXE only; just the upcard is revealed
+    pub fn deal_dealer_hand(
+        ctx: Context<DealDealerHand>,
+        computation_offset: u64,
+        _game_id: u64,
+        mxe_nonce: u128,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        card_game.require_can_draw(2)?;
+        // Redealing would silently replace a hand that is still in play
+        require!(
+            card_game.dealer_hand_size == 0 || card_game.dealer_played,
+            ErrorCode::DealerHandInPlay
+        );
+
+        let args = vec![
+            // Nonce for the dealer hand encryption
+            Argument::PlaintextU128(mxe_nonce),
+            // Deck
+            Argument::PlaintextU128(card_game.deck_nonce),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
+            // Cards already dealt
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::DealDealerHand)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![DealDealerHandCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "deal_dealer_hand")]
+    pub fn deal_dealer_hand_callback(
+        ctx: Context<DealDealerHandCallback>,
+        output: ComputationOutputs<DealDealerHandOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::DealDealerHand,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(DealDealerHandOutput {
+                field_0:
+                    DealDealerHandOutputStruct0 {
+                        field_0: dealer_hand,
+                        field_1: upcard,
+                    },
+            }) => (dealer_hand, upcard),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::DealDealerHand,
+                )
+            }
+        };
+
+        let dealer_hand_nonce = o.0.nonce;
+        let dealer_hand: [u8; 32] = o.0.ciphertexts[0];
+        let upcard: u8 = o.1;
+
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.dealer_hand = dealer_hand;
+        card_game.dealer_hand_nonce = dealer_hand_nonce;
+        card_game.dealer_hand_size = 2;
+        card_game.dealer_upcard = upcard;
+        card_game.dealer_cards = [53; 11];
+        card_game.dealer_total = 0;
+        card_game.dealer_played = false;
+        card_game.blackjack_outcomes = [0; MAX_SEATS];
+        card_game.cards_dealt += 2;
+
+        emit!(DealerHandDealtEvent {
+            game_id: card_game.game_id,
+            upcard,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::DealDealerHand,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for a blackjack hit
+    pub fn init_player_hit_comp_def(ctx: Context<InitPlayerHitCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Draws the next card from the deck into a seat's blackjack hand.
+    /// The new total stays hidden; only a bust is revealed
+    pub fn player_hit(
+        ctx: Context<PlayerHit>,
+        computation_offset: u64,
+        _game_id: u64,
+        seat: u8,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        let player = &card_game.seats[seat as usize];
+        require!(!player.stood && !player.busted, ErrorCode::SeatFinished);
+        require!(
+            (player.hole_cards_size as usize) < MAX_HOLE_CARDS,
+            ErrorCode::HandFull
+        );
+        require!(
+            card_game.dealer_hand_size > 0,
+            ErrorCode::DealerHandNotDealt
+        );
+        require!(!card_game.dealer_played, ErrorCode::DealerAlreadyPlayed);
+        card_game.require_can_draw(1)?;
+
+        let args = vec![
+            // Deck
+            Argument::PlaintextU128(card_game.deck_nonce),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
+            // Player's current hand
+            Argument::ArcisPubkey(player.player_enc_pubkey),
+            Argument::PlaintextU128(player.hole_cards_nonce),
+            Argument::Account(card_game.key(), seat_hole_cards_offset(seat), 32),
+            // Hand size
+            Argument::PlaintextU8(player.hole_cards_size),
+            // Cards already dealt
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
+            // Seat the hand belongs to
+            Argument::PlaintextU8(seat),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::PlayerHit)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![PlayerHitCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "player_hit")]
+    pub fn player_hit_callback(
+        ctx: Context<PlayerHitCallback>,
+        output: ComputationOutputs<PlayerHitOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::PlayerHit,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(PlayerHitOutput {
+                field_0:
+                    PlayerHitOutputStruct0 {
+                        field_0: hole_cards,
+                        field_1: total_cards,
+                        field_2: busted,
+                        field_3: seat,
+                    },
+            }) => (hole_cards, total_cards, busted, seat),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::PlayerHit,
+                )
+            }
+        };
+
+        let hole_cards_nonce = o.0.nonce;
+        let hole_cards: [u8; 32] = o.0.ciphertexts[0];
+        let total_cards: u8 = o.1;
+        let busted: bool = o.2;
+        let seat: u8 = o.3;
+
+        let card_game = &mut ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        let player = &mut card_game.seats[seat as usize];
+        let new_cards = total_cards.saturating_sub(player.hole_cards_size);
+        player.hole_cards = hole_cards;
+        player.hole_cards_nonce = hole_cards_nonce;
+        player.hole_cards_size = total_cards;
+        player.busted = busted;
+        card_game.cards_dealt += new_cards as u16;
+
+        emit!(PlayerHitEvent {
+            game_id: card_game.game_id,
+            seat,
+            hole_cards,
+            hole_cards_nonce,
+            total_hole_cards: total_cards,
+            busted,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::PlayerHit,
+            true,
+        )
+    }
+
+    /// Ends a seat's blackjack turn without drawing
+    pub fn player_stand(ctx: Context<PlayerStand>, _game_id: u64, seat: u8) -> Result<()> {
+        let card_game = &mut ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        card_game.require_idle()?;
+        let player = &mut card_game.seats[seat as usize];
+        require!(!player.stood && !player.busted, ErrorCode::SeatFinished);
+        player.stood = true;
+
+        emit!(PlayerStoodEvent {
+            game_id: card_game.game_id,
+            seat,
+        });
+
+        Ok(())
+    }
+
+    /// Hands control of the game to a new authority, e.g. to delegate it to a game program
+    pub fn transfer_authority(
+        ctx: Context<TransferAuthority>,
+        _game_id: u64,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let card_game = &mut ctx.accounts.card_game;
+        let previous_authority = card_game.authority;
+        card_game.authority = new_authority;
+
+        emit!(AuthorityTransferredEvent {
+            game_id: card_game.game_id,
+            previous_authority,
+            new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Releases a game stuck on a computation whose callback never landed, once
+    /// COMPUTATION_TIMEOUT_SLOTS have passed since it was queued. Whatever the
+    /// computation would have done is dropped; a late callback is rejected
+    pub fn cancel_pending_computation(
+        ctx: Context<CancelPendingComputation>,
+        _game_id: u64,
+    ) -> Result<()> {
+        let card_game = &mut ctx.accounts.card_game;
+        let pending = card_game
+            .pending_computation
+            .ok_or(ErrorCode::NoPendingComputation)?;
+        require!(
+            Clock::get()?.slot >= pending.queued_slot + COMPUTATION_TIMEOUT_SLOTS,
+            ErrorCode::ComputationNotTimedOut
+        );
+        card_game.pending_computation = None;
+
+        emit!(ComputationCancelledEvent {
+            game_id: card_game.game_id,
+            offset: pending.offset,
+            kind: pending.kind,
+        });
+
+        Ok(())
+    }
+
+    /// Hands the notification a callback recorded to the consumer program that
+    /// registered for it, by invoking its `on_card_shuffler_callback`. Anyone
+    /// can send it, with the consumer program and the accounts forwarded at
+    /// queue time as remaining accounts.
+    ///
+    /// The callback has already committed the game's update, so a consumer
+    /// that fails only fails this instruction and the notification stays
+    /// pending for another attempt
+    pub fn deliver_notification<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeliverNotification<'info>>,
+        _game_id: u64,
+    ) -> Result<()> {
+        let card_game = &mut ctx.accounts.card_game;
+        let pending = card_game
+            .pending_notification
+            .take()
+            .ok_or(ErrorCode::NoPendingNotification)?;
+
+        let [consumer_program, forwarded @ ..] = ctx.remaining_accounts else {
+            return err!(ErrorCode::InvalidConsumerProgram);
+        };
+        require_keys_eq!(
+            consumer_program.key(),
+            pending.consumer_program,
+            ErrorCode::InvalidConsumerProgram
+        );
+        require!(
+            forwarded_accounts_hash(forwarded) == pending.accounts_hash,
+            ErrorCode::ForwardedAccountsMismatch
+        );
+
+        invoke_consumer(
+            card_game,
+            &ctx.accounts.notifier,
+            consumer_program,
+            forwarded,
+            pending.notification,
+        )
+    }
+
+    /// Closes the game and returns its rent to `destination`, freeing the
+    /// `game_id` for a new game. Refused while a computation is in flight, as
+    /// its callback would have no account to write to
+    pub fn close_card_game(ctx: Context<CloseCardGame>, _game_id: u64) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        card_game.require_idle()?;
+
+        emit!(CardGameClosedEvent {
+            game_id: card_game.game_id,
+            destination: ctx.accounts.destination.key(),
+            lamports: card_game.to_account_info().lamports(),
+            num_seats: card_game.num_seats,
+            cards_dealt: card_game.cards_dealt,
+            deck_size: card_game.deck_size,
+            community_cards_size: card_game.community_cards_size,
+        });
+
+        Ok(())
+    }
+
+    /// Initializes the computation definition for playing out the dealer's hand
+    pub fn init_dealer_play_comp_def(ctx: Context<InitDealerPlayCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Plays out the dealer's blackjack hand (hitting on soft 17) and settles
+    /// every seat holding a hand. Can only run once all seats have stood or
+    /// busted, and once per dealer hand. Enough cards must remain for the
+    /// longest possible dealer hand, since the circuit cannot stop short
+    pub fn dealer_play(
+        ctx: Context<DealerPlay>,
+        computation_offset: u64,
+        _game_id: u64,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        card_game.require_can_draw(MAX_DEALER_DRAWS)?;
+        require!(
+            card_game.dealer_hand_size == 2,
+            ErrorCode::DealerHandNotDealt
+        );
+        require!(!card_game.dealer_played, ErrorCode::DealerAlreadyPlayed);
+
+        let mut contenders: u16 = 0;
+        for seat in 0..card_game.num_seats {
+            let player = &card_game.seats[seat as usize];
+            if player.hole_cards_size >= 2 {
+                require!(
+                    player.stood || player.busted,
+                    ErrorCode::PlayersStillActing
+                );
+                contenders |= 1 << seat;
+            }
+        }
+
+        let mut args = Vec::with_capacity(MAX_SEATS * 4 + 7);
+        // Deck
+        args.push(Argument::PlaintextU128(card_game.deck_nonce));
+        args.push(Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN));
+        // Dealer's hand
+        args.push(Argument::PlaintextU128(card_game.dealer_hand_nonce));
+        args.push(Argument::Account(card_game.key(), DEALER_HAND_OFFSET, 32));
+        // Cards already dealt
+        args.push(Argument::PlaintextU16(card_game.cards_dealt));
+        // Cards in the deck
+        args.push(Argument::PlaintextU16(card_game.deck_size));
+        for seat in 0..MAX_SEATS as u8 {
+            // Empty seats reuse seat 0's ciphertext; they are excluded from
+            // `contenders`, so their contents never matter
+            let source = if seat < card_game.num_seats { seat } else { 0 };
+            let player = &card_game.seats[source as usize];
+            args.push(Argument::ArcisPubkey(player.player_enc_pubkey));
+            args.push(Argument::PlaintextU128(player.hole_cards_nonce));
+            args.push(Argument::Account(
+                card_game.key(),
+                seat_hole_cards_offset(source),
+                32,
+            ));
+        }
+        // Hand sizes, used to tell naturals apart
+        for seat in 0..MAX_SEATS {
+            args.push(Argument::PlaintextU8(card_game.seats[seat].hole_cards_size));
+        }
+        // Seats being settled
+        args.push(Argument::PlaintextU16(contenders));
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::DealerPlay)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![DealerPlayCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "dealer_play")]
+    pub fn dealer_play_callback(
+        ctx: Context<DealerPlayCallback>,
+        output: ComputationOutputs<DealerPlayOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::DealerPlay,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(DealerPlayOutput {
+                field_0:
+                    DealerPlayOutputStruct0 {
+                        field_0: dealer_cards,
+                        field_1: dealer_total,
+                        field_2: cards_drawn,
+                        field_3: outcomes,
+                    },
+            }) => (dealer_cards, dealer_total, cards_drawn, outcomes),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::DealerPlay,
+                )
+            }
+        };
+
+        let dealer_cards: [u8; 11] = o.0;
+        let dealer_total: u8 = o.1;
+        let cards_drawn: u8 = o.2;
+        let outcomes: [u8; MAX_SEATS] = o.3;
+
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.dealer_cards = dealer_cards;
+        card_game.dealer_total = dealer_total;
+        card_game.dealer_played = true;
+        card_game.dealer_hand_size += cards_drawn;
+        card_game.cards_dealt += cards_drawn as u16;
+        card_game.blackjack_outcomes = outcomes;
+
+        emit!(DealerPlayedEvent {
+            game_id: card_game.game_id,
+            dealer_cards,
+            dealer_total,
+            outcomes,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::DealerPlay,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for reshuffling the undealt cards
+    pub fn init_reshuffle_remaining_comp_def(
+        ctx: Context<InitReshuffleRemainingCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reshuffles the cards not yet dealt. Hands, the board and `cards_dealt`
+    /// are left as they are.
+    /// The new deck commitment is chained to the current one, so `reveal_deck`
+    /// still covers the cards dealt before the reshuffle
+    pub fn reshuffle_remaining(
+        ctx: Context<ReshuffleRemaining>,
+        computation_offset: u64,
+        _game_id: u64,
+        entropy_seeds: Vec<EntropySeed>,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        card_game.require_can_draw(0)?;
+
+        let mut args = vec![
+            // Deck
+            Argument::PlaintextU128(card_game.deck_nonce),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
+            // Commitment salt
+            Argument::PlaintextU128(card_game.deck_salt_nonce),
+            Argument::Account(card_game.key(), DECK_SALT_OFFSET, 32),
+            // Cards already dealt
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
+        ];
+        // Commitment the new one is chained to
+        for byte in card_game.deck_commitment {
+            args.push(Argument::PlaintextU8(byte));
+        }
+        push_entropy_seed_args(
+            &mut args,
+            &entropy_seeds,
+            card_game.seats[0].player_enc_pubkey,
+        )?;
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::ReshuffleRemaining)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![ReshuffleRemainingCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "reshuffle_remaining")]
+    pub fn reshuffle_remaining_callback(
+        ctx: Context<ReshuffleRemainingCallback>,
+        output: ComputationOutputs<ReshuffleRemainingOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::ReshuffleRemaining,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(ReshuffleRemainingOutput {
+                field_0:
+                    ReshuffleRemainingOutputStruct0 {
+                        field_0: deck,
+                        field_1: salt,
+                        field_2: commitment,
+                    },
+            }) => (deck, salt, commitment),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::ReshuffleRemaining,
+                )
+            }
+        };
+
+        let deck_commitment: [u8; 32] = o.2;
+
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.deck = o.0.ciphertexts;
+        card_game.deck_nonce = o.0.nonce;
+        card_game.deck_salt = o.1.ciphertexts[0];
+        card_game.deck_salt_nonce = o.1.nonce;
+        let previous_deck_commitment = card_game.deck_commitment;
+        card_game.previous_deck_commitment = Some(previous_deck_commitment);
+        card_game.deck_commitment = deck_commitment;
+
+        emit!(DeckReshuffledEvent {
+            game_id: card_game.game_id,
+            cards_remaining: card_game.deck_size - card_game.cards_dealt,
+            deck_commitment,
+            previous_deck_commitment,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::ReshuffleRemaining,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for discarding and drawing
+    pub fn init_discard_and_draw_comp_def(
+        ctx: Context<InitDiscardAndDrawCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Discards the cards at the hand positions set in `discard_mask` (bit i =
+    /// position i) and replaces them from the deck, as in five-card draw. The
+    /// discards go to the MXE-encrypted discard pile
+    pub fn discard_and_draw(
+        ctx: Context<DiscardAndDraw>,
+        computation_offset: u64,
+        _game_id: u64,
+        seat: u8,
+        discard_mask: u16,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        let player = &card_game.seats[seat as usize];
+        require!(
+            discard_mask != 0 && discard_mask >> player.hole_cards_size == 0,
+            ErrorCode::InvalidDiscard
+        );
+        card_game.require_can_draw(discard_mask.count_ones() as u16)?;
+
+        let args = vec![
+            // Deck
+            Argument::PlaintextU128(card_game.deck_nonce),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
+            // Hand
+            Argument::ArcisPubkey(player.player_enc_pubkey),
+            Argument::PlaintextU128(player.hole_cards_nonce),
+            Argument::Account(card_game.key(), seat_hole_cards_offset(seat), 32),
+            // Discard pile
+            Argument::PlaintextU128(card_game.discard_pile_nonce),
+            Argument::Account(card_game.key(), DISCARD_PILE_OFFSET, DECK_LEN),
+            // Hand size
+            Argument::PlaintextU8(player.hole_cards_size),
+            // Positions to discard
+            Argument::PlaintextU16(discard_mask),
+            // Cards already in the discard pile
+            Argument::PlaintextU16(card_game.discard_pile_size),
+            // Cards already dealt
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
+            // Seat the hand belongs to
+            Argument::PlaintextU8(seat),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::DiscardAndDraw)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![DiscardAndDrawCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "discard_and_draw")]
+    pub fn discard_and_draw_callback(
+        ctx: Context<DiscardAndDrawCallback>,
+        output: ComputationOutputs<DiscardAndDrawOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::DiscardAndDraw,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(DiscardAndDrawOutput {
+                field_0:
+                    DiscardAndDrawOutputStruct0 {
+                        field_0: hand,
+                        field_1: discard_pile,
+                        field_2: cards_drawn,
+                        field_3: seat,
+                    },
+            }) => (hand, discard_pile, cards_drawn, seat),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::DiscardAndDraw,
+                )
+            }
+        };
+
+        let hole_cards_nonce = o.0.nonce;
+        let hole_cards: [u8; 32] = o.0.ciphertexts[0];
+        let cards_drawn: u8 = o.2;
+        let seat: u8 = o.3;
+
+        let card_game = &mut ctx.accounts.card_game;
+        require!(seat < card_game.num_seats, ErrorCode::InvalidSeat);
+        let player = &mut card_game.seats[seat as usize];
+        player.hole_cards = hole_cards;
+        player.hole_cards_nonce = hole_cards_nonce;
+        card_game.discard_pile = o.1.ciphertexts;
+        card_game.discard_pile_nonce = o.1.nonce;
+        card_game.discard_pile_size += cards_drawn as u16;
+        card_game.cards_dealt += cards_drawn as u16;
+
+        emit!(CardsDiscardedEvent {
+            game_id: card_game.game_id,
+            seat,
+            hole_cards,
+            hole_cards_nonce,
+            num_discarded: cards_drawn,
+            discard_pile_size: card_game.discard_pile_size,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::DiscardAndDraw,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for revealing the deck
+    pub fn init_reveal_deck_comp_def(ctx: Context<InitRevealDeckCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Publishes the deck order and the commitment salt so anyone can check the
+    /// deck against `deck_commitment` and every card dealt from it. No more
+    /// cards can be dealt afterwards. After a reshuffle the check goes through
+    /// `previous_deck_commitment`, which chains back to the original shuffle.
+    ///
+    /// The deck shows every card dealt from it, so this is an end-of-game audit:
+    /// every hand must be emptied first (`change_hand` for the seats,
+    /// `set_house_key` for the house) and any blackjack dealer hand played out
+    pub fn reveal_deck(
+        ctx: Context<RevealDeck>,
+        computation_offset: u64,
+        _game_id: u64,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        require!(card_game.deck_shuffled, ErrorCode::DeckNotShuffled);
+        require!(!card_game.deck_revealed, ErrorCode::DeckRevealed);
+        require!(
+            !card_game.holds_hidden_cards(),
+            ErrorCode::HiddenCardsInPlay
+        );
+        card_game.require_idle()?;
+
+        let args = vec![
+            // Deck
+            Argument::PlaintextU128(card_game.deck_nonce),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
+            // Commitment salt
+            Argument::PlaintextU128(card_game.deck_salt_nonce),
+            Argument::Account(card_game.key(), DECK_SALT_OFFSET, 32),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::RevealDeck)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealDeckCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "reveal_deck")]
+    pub fn reveal_deck_callback(
+        ctx: Context<RevealDeckCallback>,
+        output: ComputationOutputs<RevealDeckOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::RevealDeck,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(RevealDeckOutput {
+                field_0:
+                    RevealDeckOutputStruct0 {
+                        field_0: deck,
+                        field_1: salt,
+                    },
+            }) => (deck, salt),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::RevealDeck,
+                )
+            }
+        };
+
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.deck_revealed = true;
+
+        emit!(DeckRevealedEvent {
+            game_id: card_game.game_id,
+            deck: o.0,
+            deck_salt: o.1,
+            deck_commitment: card_game.deck_commitment,
+            previous_deck_commitment: card_game.previous_deck_commitment,
+            deck_size: card_game.deck_size,
+            cards_dealt: card_game.cards_dealt,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::RevealDeck,
+            true,
+        )
+    }
+
+    /// Assigns the house seat's x25519 key and empties its hand, e.g. before
+    /// each round of a house-banked game
+    pub fn set_house_key(
+        ctx: Context<SetHouseKey>,
+        _game_id: u64,
+        house_enc_pubkey: [u8; 32],
+    ) -> Result<()> {
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.require_idle()?;
+        card_game.house = Seat {
+            player_enc_pubkey: house_enc_pubkey,
+            ..Seat::default()
+        };
+
+        emit!(HouseKeySetEvent {
+            game_id: card_game.game_id,
+            house_enc_pubkey,
+        });
+
+        Ok(())
+    }
+
+    /// Initializes the computation definition for dealing to the house
+    pub fn init_deal_house_cards_comp_def(ctx: Context<InitDealHouseCardsCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Deals `num_new_cards` to the house seat, after burning `num_burn`.
+    /// `house_nonce` is the fresh nonce the updated hand is encrypted under
+    pub fn deal_house_cards(
+        ctx: Context<DealHouseCards>,
+        computation_offset: u64,
+        _game_id: u64,
+        house_nonce: u128,
+        num_new_cards: u8,
+        num_burn: u8,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        let house = &card_game.house;
+        require!(
+            house.player_enc_pubkey != [0; 32],
+            ErrorCode::HouseKeyNotSet
+        );
+        card_game.require_can_draw(num_new_cards as u16 + num_burn as u16)?;
+        require!(
+            house.hole_cards_size as usize + num_new_cards as usize <= MAX_HOLE_CARDS,
+            ErrorCode::HandFull
+        );
+
+        let args = vec![
+            // House key, with the nonce for the updated hand
+            Argument::ArcisPubkey(house.player_enc_pubkey),
+            Argument::PlaintextU128(house_nonce),
+            // Deck
+            Argument::PlaintextU128(card_game.deck_nonce),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
+            // Existing house hand
+            Argument::ArcisPubkey(house.player_enc_pubkey),
+            Argument::PlaintextU128(house.hole_cards_nonce),
+            Argument::Account(card_game.key(), HOUSE_HAND_OFFSET, 32),
+            // Hand size
+            Argument::PlaintextU8(house.hole_cards_size),
+            // New cards to add
+            Argument::PlaintextU8(num_new_cards),
+            // Cards to burn first
+            Argument::PlaintextU8(num_burn),
+            // Cards already dealt
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::DealHouseCards)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![DealHouseCardsCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "deal_house_cards")]
+    pub fn deal_house_cards_callback(
+        ctx: Context<DealHouseCardsCallback>,
+        output: ComputationOutputs<DealHouseCardsOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::DealHouseCards,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(DealHouseCardsOutput {
+                field_0:
+                    DealHouseCardsOutputStruct0 {
+                        field_0: house_hand,
+                        field_1: new_hand_size,
+                        field_2: num_burned,
+                    },
+            }) => (house_hand, new_hand_size, num_burned),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::DealHouseCards,
+                )
+            }
+        };
+
+        let hole_cards_nonce = o.0.nonce;
+        let hole_cards: [u8; 32] = o.0.ciphertexts[0];
+        let new_size: u8 = o.1;
+        let num_burned: u8 = o.2;
+
+        let card_game = &mut ctx.accounts.card_game;
+        let house = &mut card_game.house;
+        let cards_added = new_size.saturating_sub(house.hole_cards_size);
+
+        house.hole_cards = hole_cards;
+        house.hole_cards_nonce = hole_cards_nonce;
+        house.hole_cards_size = new_size;
+        card_game.cards_dealt += cards_added as u16 + num_burned as u16;
+        card_game.cards_burned += num_burned as u16;
+
+        emit!(HouseCardsDealtEvent {
+            game_id: card_game.game_id,
+            hole_cards,
+            hole_cards_nonce,
+            total_hole_cards: new_size,
+            num_burned,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::DealHouseCards,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for revealing the house hand
+    pub fn init_reveal_house_cards_comp_def(
+        ctx: Context<InitRevealHouseCardsCompDef>,
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Reveals the house hand slots selected by `reveal_mask` (bit i for slot i)
+    pub fn reveal_house_cards(
+        ctx: Context<RevealHouseCards>,
+        computation_offset: u64,
+        _game_id: u64,
+        reveal_mask: u16,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        card_game.require_idle()?;
+        require!(
+            reveal_mask >> MAX_HOLE_CARDS == 0,
+            ErrorCode::RevealLimitExceeded
+        );
+        let house = &card_game.house;
+        require!(
+            house.player_enc_pubkey != [0; 32],
+            ErrorCode::HouseKeyNotSet
+        );
+
+        let args = vec![
+            // Hand to reveal
+            Argument::ArcisPubkey(house.player_enc_pubkey),
+            Argument::PlaintextU128(house.hole_cards_nonce),
+            Argument::Account(card_game.key(), HOUSE_HAND_OFFSET, 32),
+            // Hand size
+            Argument::PlaintextU8(house.hole_cards_size),
+            // Slots to reveal
+            Argument::PlaintextU16(reveal_mask),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::RevealHouseCards)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealHouseCardsCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "reveal_house_cards")]
+    pub fn reveal_house_cards_callback(
+        ctx: Context<RevealHouseCardsCallback>,
+        output: ComputationOutputs<RevealHouseCardsOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::RevealHouseCards,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(RevealHouseCardsOutput {
+                field_0:
+                    RevealHouseCardsOutputStruct0 {
+                        field_0: cards,
+                        field_1: num,
+                    },
+            }) => (cards, num),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::RevealHouseCards,
+                )
+            }
+        };
+
+        let revealed_cards = o.0;
+        let num_revealed = o.1;
+
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.house.revealed_cards = revealed_cards;
+        card_game.house.revealed_cards_size = num_revealed;
+
+        emit!(HouseCardsRevealedEvent {
+            game_id: card_game.game_id,
+            revealed_cards,
+            num_revealed,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::RevealHouseCards,
+            true,
+        )
+    }
+
+    /// Initializes the computation definition for playing a baccarat coup
+    pub fn init_play_baccarat_comp_def(ctx: Context<InitPlayBaccaratCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Plays a baccarat coup from the shoe: deals Player and Banker, applies the
+    /// third-card tableau in MPC and records both hands and the outcome on the
+    /// game's BaccaratRound account (created on the first coup)
+    pub fn play_baccarat(
+        ctx: Context<PlayBaccarat>,
+        computation_offset: u64,
+        game_id: u64,
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        require!(card_game.deck_spec.jokers == 0, ErrorCode::InvalidDeckSpec);
+        card_game.require_can_draw(MAX_BACCARAT_CARDS)?;
+
+        let args = vec![
+            // Deck
+            Argument::PlaintextU128(card_game.deck_nonce),
+            Argument::Account(card_game.key(), DECK_OFFSET, DECK_LEN),
+            // Cards already dealt
+            Argument::PlaintextU16(card_game.cards_dealt),
+            // Cards in the deck
+            Argument::PlaintextU16(card_game.deck_size),
+        ];
+
+        let baccarat_round = &mut ctx.accounts.baccarat_round;
+        baccarat_round.game_id = game_id;
+        baccarat_round.bump = ctx.bumps.baccarat_round;
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::PlayBaccarat)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // The round account goes right after the CardGame and computation
+        // account, ahead of any consumer accounts
+        let mut accounts = callback_accounts(
+            ctx.accounts.card_game.key(),
+            ctx.accounts.computation_account.key(),
+            ctx.remaining_accounts,
+        )?;
+        accounts.insert(
+            2,
+            CallbackAccount {
+                pubkey: ctx.accounts.baccarat_round.key(),
+                is_writable: true,
+            },
+        );
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![PlayBaccaratCallback::callback_ix(&accounts)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "play_baccarat")]
+    pub fn play_baccarat_callback(
+        ctx: Context<PlayBaccaratCallback>,
+        output: ComputationOutputs<PlayBaccaratOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::PlayBaccarat,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(PlayBaccaratOutput {
+                field_0:
+                    PlayBaccaratOutputStruct0 {
+                        field_0: player_cards,
+                        field_1: player_cards_size,
+                        field_2: banker_cards,
+                        field_3: banker_cards_size,
+                        field_4: player_total,
+                        field_5: banker_total,
+                        field_6: outcome,
+                    },
+            }) => (
+                player_cards,
+                player_cards_size,
+                banker_cards,
+                banker_cards_size,
+                player_total,
+                banker_total,
+                outcome,
+            ),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::PlayBaccarat,
+                )
+            }
+        };
+
+        let card_game = &mut ctx.accounts.card_game;
+        card_game.cards_dealt += o.1 as u16 + o.3 as u16;
+
+        let baccarat_round = &mut ctx.accounts.baccarat_round;
+        baccarat_round.coups_played += 1;
+        baccarat_round.player_cards = o.0;
+        baccarat_round.player_cards_size = o.1;
+        baccarat_round.banker_cards = o.2;
+        baccarat_round.banker_cards_size = o.3;
+        baccarat_round.player_total = o.4;
+        baccarat_round.banker_total = o.5;
+        baccarat_round.outcome = o.6;
+
+        emit!(BaccaratPlayedEvent {
+            game_id: card_game.game_id,
+            coup: baccarat_round.coups_played,
+            player_cards: o.0,
+            player_cards_size: o.1,
+            banker_cards: o.2,
+            banker_cards_size: o.3,
+            player_total: o.4,
+            banker_total: o.5,
+            outcome: o.6,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::PlayBaccarat,
+            true,
+        )
+    }
+
+    /// Closes a game's BaccaratRound account and returns its rent to `destination`
+    pub fn close_baccarat_round(ctx: Context<CloseBaccaratRound>, _game_id: u64) -> Result<()> {
+        ctx.accounts.card_game.require_idle()
+    }
+
+    /// Initializes the computation definition for passing cards between seats
+    pub fn init_pass_cards_comp_def(ctx: Context<InitPassCardsCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Passes `num_cards` cards from `sender_seat` to `recipient_seat` without
+    /// revealing them. The sender picks the cards with `selection_ciphertext`, a
+    /// u16 slot mask (bit i for slot i) encrypted with their key under
+    /// `selection_nonce`. The passed cards are re-encrypted to the recipient's
+    /// key; a selection that does not pick exactly `num_cards` held cards moves
+    /// nothing
+    pub fn pass_cards(
+        ctx: Context<PassCards>,
+        computation_offset: u64,
+        _game_id: u64,
+        sender_seat: u8,
+        recipient_seat: u8,
+        num_cards: u8,
+        selection_nonce: u128,
+        selection_ciphertext: [u8; 32],
+    ) -> Result<()> {
+        let card_game = &ctx.accounts.card_game;
+        require!(
+            sender_seat < card_game.num_seats
+                && recipient_seat < card_game.num_seats
+                && sender_seat != recipient_seat,
+            ErrorCode::InvalidSeat
+        );
+        let sender = &card_game.seats[sender_seat as usize];
+        let recipient = &card_game.seats[recipient_seat as usize];
+        require!(
+            num_cards > 0 && num_cards <= sender.hole_cards_size,
+            ErrorCode::InvalidPass
+        );
+        require!(
+            recipient.hole_cards_size as usize + num_cards as usize <= MAX_HOLE_CARDS,
+            ErrorCode::HandFull
+        );
+        card_game.require_idle()?;
+
+        let args = vec![
+            // Sender hand
+            Argument::ArcisPubkey(sender.player_enc_pubkey),
+            Argument::PlaintextU128(sender.hole_cards_nonce),
+            Argument::Account(card_game.key(), seat_hole_cards_offset(sender_seat), 32),
+            // Recipient hand
+            Argument::ArcisPubkey(recipient.player_enc_pubkey),
+            Argument::PlaintextU128(recipient.hole_cards_nonce),
+            Argument::Account(card_game.key(), seat_hole_cards_offset(recipient_seat), 32),
+            // Slots to pass, encrypted by the sender
+            Argument::ArcisPubkey(sender.player_enc_pubkey),
+            Argument::PlaintextU128(selection_nonce),
+            Argument::EncryptedU16(selection_ciphertext),
+            // Hand sizes
+            Argument::PlaintextU8(sender.hole_cards_size),
+            Argument::PlaintextU8(recipient.hole_cards_size),
+            // Number of cards to pass
+            Argument::PlaintextU8(num_cards),
+            // Seats the cards move between
+            Argument::PlaintextU8(sender_seat),
+            Argument::PlaintextU8(recipient_seat),
+        ];
+
+        ctx.accounts
+            .card_game
+            .begin_computation(computation_offset, ComputationKind::PassCards)?;
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![PassCardsCallback::callback_ix(&callback_accounts(
+                ctx.accounts.card_game.key(),
+                ctx.accounts.computation_account.key(),
+                ctx.remaining_accounts,
+            )?)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback handler
+    #[arcium_callback(encrypted_ix = "pass_cards")]
+    pub fn pass_cards_callback(
+        ctx: Context<PassCardsCallback>,
+        output: ComputationOutputs<PassCardsOutput>,
+    ) -> Result<()> {
+        ctx.accounts.card_game.complete_computation(
+            ctx.accounts.computation_account.key(),
+            ComputationKind::PassCards,
+        )?;
+
+        let o = match output {
+            ComputationOutputs::Success(PassCardsOutput {
+                field_0:
+                    PassCardsOutputStruct0 {
+                        field_0: sender_hand,
+                        field_1: recipient_hand,
+                        field_2: cards_passed,
+                        field_3: sender_seat,
+                        field_4: recipient_seat,
+                    },
+            }) => (
+                sender_hand,
+                recipient_hand,
+                cards_passed,
+                sender_seat,
+                recipient_seat,
+            ),
+            _ => {
+                return computation_aborted(
+                    &mut ctx.accounts.card_game,
+                    ctx.remaining_accounts,
+                    ComputationKind::PassCards,
+                )
+            }
+        };
+
+        let cards_passed: u8 = o.2;
+        let sender_seat: u8 = o.3;
+        let recipient_seat: u8 = o.4;
+
+        let card_game = &mut ctx.accounts.card_game;
+        require!(
+            sender_seat < card_game.num_seats && recipient_seat < card_game.num_seats,
+            ErrorCode::InvalidSeat
+        );
+        let sender = &mut card_game.seats[sender_seat as usize];
+        sender.hole_cards = o.0.ciphertexts[0];
+        sender.hole_cards_nonce = o.0.nonce;
+        sender.hole_cards_size -= cards_passed;
+        let recipient = &mut card_game.seats[recipient_seat as usize];
+        recipient.hole_cards = o.1.ciphertexts[0];
+        recipient.hole_cards_nonce = o.1.nonce;
+        recipient.hole_cards_size += cards_passed;
+
+        emit!(CardsPassedEvent {
+            game_id: card_game.game_id,
+            sender_seat,
+            recipient_seat,
+            sender_hole_cards: o.0.ciphertexts[0],
+            sender_hole_cards_nonce: o.0.nonce,
+            recipient_hole_cards: o.1.ciphertexts[0],
+            recipient_hole_cards_nonce: o.1.nonce,
+            num_passed: cards_passed,
+        });
+
+        notify_consumer(
+            &mut ctx.accounts.card_game,
+            ctx.remaining_accounts,
+            ComputationKind::PassCards,
+            true,
+        )
+    }
+}
+
+/// Handles an aborted computation. The callback still succeeds so the pending
+/// computation stays cleared and the game is not left locked; the abort is reported
+/// as an event and to the consumer program, if one was registered
+fn computation_aborted(
+    card_game: &mut Account<CardGame>,
+    callback_accounts: &[AccountInfo],
+    kind: ComputationKind,
+) -> Result<()> {
+    emit!(ComputationAbortedEvent {
+        game_id: card_game.game_id,
+    });
+
+    notify_consumer(card_game, callback_accounts, kind, false)
+}
+
+/// Builds the accounts passed to a computation's callback. Any remaining accounts
+/// given to the queueing instruction register a consumer program to notify once the
+/// callback has updated the CardGame: the first is the consumer program and the rest
+/// are forwarded to its `on_card_shuffler_callback` instruction
+fn callback_accounts(
+    card_game: Pubkey,
+    computation_account: Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Vec<CallbackAccount>> {
+    let mut accounts = vec![
+        CallbackAccount {
+            pubkey: card_game,
+            is_writable: true,
+        },
+        CallbackAccount {
+            pubkey: computation_account,
+            is_writable: false,
+        },
+    ];
+
+    if let Some(consumer_program) = remaining_accounts.first() {
+        require!(
+            consumer_program.executable,
+            ErrorCode::InvalidConsumerProgram
+        );
+
+        accounts.extend(remaining_accounts.iter().map(|account| CallbackAccount {
+            pubkey: account.key(),
+            is_writable: account.is_writable,
+        }));
+    }
+
+    Ok(accounts)
+}
+
+/// Records a notification for the consumer program registered at queue time,
+/// if any, for `deliver_notification` to hand over. `callback_accounts` holds
+/// the consumer program and the forwarded accounts, in that order.
+///
+/// The consumer is not invoked here: the runtime aborts the whole transaction
+/// when a CPI fails, so a failing consumer would revert the callback and lose
+/// the game's update with it. A notification that was never delivered is
+/// replaced by the next one
+fn notify_consumer(
+    card_game: &mut Account<CardGame>,
+    callback_accounts: &[AccountInfo],
+    kind: ComputationKind,
+    success: bool,
+) -> Result<()> {
+    let [consumer_program, forwarded @ ..] = callback_accounts else {
+        return Ok(());
+    };
+
+    card_game.pending_notification = Some(PendingNotification {
+        consumer_program: consumer_program.key(),
+        accounts_hash: forwarded_accounts_hash(forwarded),
+        notification: CardShufflerNotification {
+            game_id: card_game.game_id,
+            kind,
+            success,
+        },
+    });
+
+    Ok(())
+}
+
+/// SHA-256 of the forwarded accounts' keys, each followed by a writable flag
+/// byte, so a delivery passes the consumer the accounts registered at queue time
+fn forwarded_accounts_hash(forwarded: &[AccountInfo]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(forwarded.len() * 33);
+    for account in forwarded {
+        data.extend_from_slice(account.key.as_ref());
+        data.push(account.is_writable as u8);
+    }
+    hash(&data).to_bytes()
+}
+
+/// Invokes the consumer's `on_card_shuffler_callback` with `notification`. The
+/// consumer receives the notifier PDA as a signer (proving the call came from
+/// card_shuffler), the CardGame, and the forwarded accounts
+fn invoke_consumer<'info>(
+    card_game: &Account<'info, CardGame>,
+    notifier: &AccountInfo<'info>,
+    consumer_program: &AccountInfo<'info>,
+    forwarded: &[AccountInfo<'info>],
+    notification: CardShufflerNotification,
+) -> Result<()> {
+    let (notifier_key, notifier_bump) =
+        Pubkey::find_program_address(&[NOTIFIER_SEED], &crate::ID);
+    require_keys_eq!(notifier.key(), notifier_key, ErrorCode::InvalidNotifier);
+
+    // Persist the taken notification so the consumer cannot have it delivered
+    // again, and reads the game as it is now
+    card_game.exit(&crate::ID)?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(notifier_key, true),
+        AccountMeta::new_readonly(card_game.key(), false),
+    ];
+    accounts.extend(forwarded.iter().map(|account| {
+        if account.is_writable {
+            AccountMeta::new(account.key(), false)
+        } else {
+            AccountMeta::new_readonly(account.key(), false)
+        }
+    }));
+
+    let mut data = CONSUMER_CALLBACK_DISCRIMINATOR.to_vec();
+    notification.serialize(&mut data)?;
+
+    let mut account_infos = vec![notifier.clone(), card_game.to_account_info()];
+    account_infos.extend(forwarded.iter().cloned());
+    account_infos.push(consumer_program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: consumer_program.key(),
+            accounts,
+            data,
+        },
+        &account_infos,
+        &[&[NOTIFIER_SEED, &[notifier_bump]]],
+    )?;
+
+    Ok(())
+}
+
+/// Appends a shuffle's player seed arguments: the seed count, then
+/// MAX_ENTROPY_SEEDS encrypted seeds. Unused slots are filled with a zero
+/// ciphertext under `filler_pubkey`, which the circuit ignores
+fn push_entropy_seed_args(
+    args: &mut Vec<Argument>,
+    entropy_seeds: &[EntropySeed],
+    filler_pubkey: [u8; 32],
+) -> Result<()> {
+    require!(
+        entropy_seeds.len() <= MAX_ENTROPY_SEEDS,
+        ErrorCode::TooManyEntropySeeds
+    );
+
+    args.push(Argument::PlaintextU8(entropy_seeds.len() as u8));
+    for slot in 0..MAX_ENTROPY_SEEDS {
+        let seed = entropy_seeds.get(slot).copied().unwrap_or(EntropySeed {
+            enc_pubkey: filler_pubkey,
+            nonce: 0,
+            ciphertext: [0; 32],
+        });
+        args.push(Argument::ArcisPubkey(seed.enc_pubkey));
+        args.push(Argument::PlaintextU128(seed.nonce));
+        args.push(Argument::EncryptedU128(seed.ciphertext));
+    }
+
+    Ok(())
+}
+
+/// Byte offset of a seat's encrypted hole cards within the CardGame account
+fn seat_hole_cards_offset(seat: u8) -> u32 {
+    SEATS_OFFSET + seat as u32 * Seat::INIT_SPACE as u32
+}
+
+#[queue_computation_accounts("shuffle_and_deal_deck", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, game_id: u64)]
+pub struct InitializeCardGame<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SHUFFLE_AND_DEAL)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CardGame::INIT_SPACE,
+        seeds = [b"card_game".as_ref(), game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("shuffle_and_deal_deck")]
+#[derive(Accounts)]
+pub struct ShuffleAndDealDeckCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SHUFFLE_AND_DEAL)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("shuffle_and_deal_deck", payer)]
+#[derive(Accounts)]
+pub struct InitShuffleAndDealCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("store_hole_cards", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct StoreHoleCards<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_STORE_HOLE_CARDS)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("store_hole_cards")]
+#[derive(Accounts)]
+pub struct StoreHoleCardsCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_STORE_HOLE_CARDS)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("store_hole_cards", payer)]
+#[derive(Accounts)]
+pub struct InitStoreHoleCardsCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_community_cards", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct RevealCommunityCards<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_COMMUNITY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("reveal_community_cards")]
+#[derive(Accounts)]
+pub struct RevealCommunityCardsCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_COMMUNITY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("reveal_community_cards", payer)]
+#[derive(Accounts)]
+pub struct InitRevealCommunityCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("change_hand", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct ChangeHand<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHANGE_HAND)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("change_hand")]
+#[derive(Accounts)]
+pub struct ChangeHandCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHANGE_HAND)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("change_hand", payer)]
+#[derive(Accounts)]
+pub struct InitChangeHandCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("seat_player", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct SeatPlayer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SEAT_PLAYER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("seat_player")]
+#[derive(Accounts)]
+pub struct SeatPlayerCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SEAT_PLAYER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("seat_player", payer)]
+#[derive(Accounts)]
+pub struct InitSeatPlayerCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_hole_cards", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct RevealHoleCards<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_HOLE_CARDS)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("reveal_hole_cards")]
+#[derive(Accounts)]
+pub struct RevealHoleCardsCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_HOLE_CARDS)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("reveal_hole_cards", payer)]
+#[derive(Accounts)]
+pub struct InitRevealHoleCardsCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("resolve_showdown", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct ResolveShowdown<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_RESOLVE_SHOWDOWN)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("resolve_showdown")]
+#[derive(Accounts)]
+pub struct ResolveShowdownCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_RESOLVE_SHOWDOWN)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("resolve_showdown", payer)]
+#[derive(Accounts)]
+pub struct InitResolveShowdownCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("deal_dealer_hand", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct DealDealerHand<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DEAL_DEALER_HAND)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("deal_dealer_hand")]
+#[derive(Accounts)]
+pub struct DealDealerHandCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DEAL_DEALER_HAND)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("deal_dealer_hand", payer)]
+#[derive(Accounts)]
+pub struct InitDealDealerHandCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("player_hit", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct PlayerHit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_PLAYER_HIT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("player_hit")]
+#[derive(Accounts)]
+pub struct PlayerHitCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_PLAYER_HIT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("player_hit", payer)]
+#[derive(Accounts)]
+pub struct InitPlayerHitCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(_game_id: u64)]
+pub struct ClearBoard<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[derive(Accounts)]
+#[instruction(_game_id: u64)]
+pub struct PlayerStand<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[derive(Accounts)]
+#[instruction(_game_id: u64)]
+pub struct TransferAuthority<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[derive(Accounts)]
+#[instruction(_game_id: u64)]
+pub struct CancelPendingComputation<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[derive(Accounts)]
+#[instruction(_game_id: u64)]
+pub struct DeliverNotification<'info> {
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: checked against the notifier PDA, which signs the consumer call
+    pub notifier: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_game_id: u64)]
+pub struct CloseCardGame<'info> {
+    pub authority: Signer<'info>,
+    /// CHECK: only receives the reclaimed lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = destination,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[queue_computation_accounts("dealer_play", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct DealerPlay<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DEALER_PLAY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("dealer_play")]
+#[derive(Accounts)]
+pub struct DealerPlayCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DEALER_PLAY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("dealer_play", payer)]
+#[derive(Accounts)]
+pub struct InitDealerPlayCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reshuffle_remaining", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct ReshuffleRemaining<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_RESHUFFLE_REMAINING)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("reshuffle_remaining")]
+#[derive(Accounts)]
+pub struct ReshuffleRemainingCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_RESHUFFLE_REMAINING)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("reshuffle_remaining", payer)]
+#[derive(Accounts)]
+pub struct InitReshuffleRemainingCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("discard_and_draw", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct DiscardAndDraw<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DISCARD_AND_DRAW)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("discard_and_draw")]
+#[derive(Accounts)]
+pub struct DiscardAndDrawCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DISCARD_AND_DRAW)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+}
+
+#[init_computation_definition_accounts("discard_and_draw", payer)]
+#[derive(Accounts)]
+pub struct InitDiscardAndDrawCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_deck", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct RevealDeck<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_DECK)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[callback_accounts("reveal_deck")]
+#[derive(Accounts)]
+pub struct RevealDeckCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_DECK)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
 }
 
+#[init_computation_definition_accounts("reveal_deck", payer)]
+#[derive(Accounts)]
+pub struct InitRevealDeckCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
 
-#[queue_computation_accounts("shuffle_and_deal_deck", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64, game_id: u64)]
-pub struct InitializeCardGame<'info> {
+#[instruction(_game_id: u64)]
+pub struct SetHouseKey<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+}
+
+#[queue_computation_accounts("deal_house_cards", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, _game_id: u64)]
+pub struct DealHouseCards<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
         init_if_needed,
         space = 9,
@@ -381,7 +4032,7 @@ pub struct InitializeCardGame<'info> {
     /// CHECK: computation_account, checked by the arcium program.
     pub computation_account: UncheckedAccount<'info>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SHUFFLE_AND_DEAL)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DEAL_HOUSE_CARDS)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(
@@ -401,33 +4052,35 @@ pub struct InitializeCardGame<'info> {
     pub system_program: Program<'info, System>,
     pub arcium_program: Program<'info, Arcium>,
     #[account(
-        init,
-        payer = payer,
-        space = 8 + CardGame::INIT_SPACE,
-        seeds = [b"card_game".as_ref(), game_id.to_le_bytes().as_ref()],
-        bump,
+        mut,
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
     )]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
 }
 
-#[callback_accounts("shuffle_and_deal_deck")]
+#[callback_accounts("deal_house_cards")]
 #[derive(Accounts)]
-pub struct ShuffleAndDealDeckCallback<'info> {
+pub struct DealHouseCardsCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SHUFFLE_AND_DEAL)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DEAL_HOUSE_CARDS)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
     #[account(mut)]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
 }
 
-#[init_computation_definition_accounts("shuffle_and_deal_deck", payer)]
+#[init_computation_definition_accounts("deal_house_cards", payer)]
 #[derive(Accounts)]
-pub struct InitShuffleAndDealCompDef<'info> {
+pub struct InitDealHouseCardsCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -442,12 +4095,13 @@ pub struct InitShuffleAndDealCompDef<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[queue_computation_accounts("store_hole_cards", payer)]
+#[queue_computation_accounts("reveal_house_cards", payer)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64, _game_id: u64)]
-pub struct StoreHoleCards<'info> {
+pub struct RevealHouseCards<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
         init_if_needed,
         space = 9,
@@ -478,7 +4132,7 @@ pub struct StoreHoleCards<'info> {
     /// CHECK: computation_account, checked by the arcium program.
     pub computation_account: UncheckedAccount<'info>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_STORE_HOLE_CARDS)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_HOUSE_CARDS)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(
@@ -501,28 +4155,32 @@ pub struct StoreHoleCards<'info> {
         mut,
         seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
         bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
     )]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
 }
 
-#[callback_accounts("store_hole_cards")]
+#[callback_accounts("reveal_house_cards")]
 #[derive(Accounts)]
-pub struct StoreHoleCardsCallback<'info> {
+pub struct RevealHouseCardsCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_STORE_HOLE_CARDS)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_HOUSE_CARDS)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
     #[account(mut)]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
 }
 
-#[init_computation_definition_accounts("store_hole_cards", payer)]
+#[init_computation_definition_accounts("reveal_house_cards", payer)]
 #[derive(Accounts)]
-pub struct InitStoreHoleCardsCompDef<'info> {
+pub struct InitRevealHouseCardsCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -537,12 +4195,13 @@ pub struct InitStoreHoleCardsCompDef<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[queue_computation_accounts("reveal_community_cards", payer)]
+#[queue_computation_accounts("play_baccarat", payer)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64, _game_id: u64)]
-pub struct RevealCommunityCards<'info> {
+pub struct PlayBaccarat<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
         init_if_needed,
         space = 9,
@@ -573,7 +4232,7 @@ pub struct RevealCommunityCards<'info> {
     /// CHECK: computation_account, checked by the arcium program.
     pub computation_account: UncheckedAccount<'info>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_COMMUNITY)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_PLAY_BACCARAT)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(
@@ -596,28 +4255,46 @@ pub struct RevealCommunityCards<'info> {
         mut,
         seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
         bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+    #[account(
+        init_if_needed,
+        space = 8 + BaccaratRound::INIT_SPACE,
+        payer = payer,
+        seeds = [b"baccarat".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump,
     )]
-    pub card_game: Account<'info, CardGame>,
+    pub baccarat_round: Box<Account<'info, BaccaratRound>>,
 }
 
-#[callback_accounts("reveal_community_cards")]
+#[callback_accounts("play_baccarat")]
 #[derive(Accounts)]
-pub struct RevealCommunityCardsCallback<'info> {
+pub struct PlayBaccaratCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_COMMUNITY)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_PLAY_BACCARAT)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
     #[account(mut)]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"baccarat".as_ref(), card_game.game_id.to_le_bytes().as_ref()],
+        bump = baccarat_round.bump,
+    )]
+    pub baccarat_round: Box<Account<'info, BaccaratRound>>,
 }
 
-#[init_computation_definition_accounts("reveal_community_cards", payer)]
+#[init_computation_definition_accounts("play_baccarat", payer)]
 #[derive(Accounts)]
-pub struct InitRevealCommunityCompDef<'info> {
+pub struct InitPlayBaccaratCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -632,12 +4309,35 @@ pub struct InitRevealCommunityCompDef<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[queue_computation_accounts("change_hand", payer)]
+#[derive(Accounts)]
+#[instruction(_game_id: u64)]
+pub struct CloseBaccaratRound<'info> {
+    pub authority: Signer<'info>,
+    /// CHECK: only receives the reclaimed lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub card_game: Box<Account<'info, CardGame>>,
+    #[account(
+        mut,
+        close = destination,
+        seeds = [b"baccarat".as_ref(), _game_id.to_le_bytes().as_ref()],
+        bump = baccarat_round.bump,
+    )]
+    pub baccarat_round: Box<Account<'info, BaccaratRound>>,
+}
+
+#[queue_computation_accounts("pass_cards", payer)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64, _game_id: u64)]
-pub struct ChangeHand<'info> {
+pub struct PassCards<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
         init_if_needed,
         space = 9,
@@ -668,7 +4368,7 @@ pub struct ChangeHand<'info> {
     /// CHECK: computation_account, checked by the arcium program.
     pub computation_account: UncheckedAccount<'info>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHANGE_HAND)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_PASS_CARDS)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(
@@ -691,28 +4391,32 @@ pub struct ChangeHand<'info> {
         mut,
         seeds = [b"card_game".as_ref(), _game_id.to_le_bytes().as_ref()],
         bump = card_game.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
     )]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
 }
 
-#[callback_accounts("change_hand")]
+#[callback_accounts("pass_cards")]
 #[derive(Accounts)]
-pub struct ChangeHandCallback<'info> {
+pub struct PassCardsCallback<'info> {
     pub arcium_program: Program<'info, Arcium>,
     #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHANGE_HAND)
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_PASS_CARDS)
     )]
     pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
     #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
     #[account(mut)]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
+    /// CHECK: the callback's computation account, matched against the game's
+    /// pending computation in the handler
+    pub computation_account: UncheckedAccount<'info>,
 }
 
-#[init_computation_definition_accounts("change_hand", payer)]
+#[init_computation_definition_accounts("pass_cards", payer)]
 #[derive(Accounts)]
-pub struct InitChangeHandCompDef<'info> {
+pub struct InitPassCardsCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -731,35 +4435,313 @@ pub struct InitChangeHandCompDef<'info> {
 #[account]
 #[derive(InitSpace)]
 pub struct CardGame {
-    /// Encrypted deck split into 3 chunks (52 cards encoded in base-64)
-    pub deck: [[u8; 32]; 3],
+    /// Encrypted deck split into 16 chunks (up to 324 cards encoded in base-64)
+    pub deck: [[u8; 32]; DECK_LIMBS],
     /// Cryptographic nonce for deck encryption
     pub deck_nonce: u128,
-    /// Player's encrypted hole cards
-    pub hole_cards: [u8; 32],
-    /// Cryptographic nonce for hole cards encryption
-    pub hole_cards_nonce: u128,
-    /// Number of hole cards currently held
-    pub hole_cards_size: u8,
+    /// Blackjack dealer's hand, encrypted to the MXE only
+    pub dealer_hand: [u8; 32],
+    /// Cryptographic nonce for dealer hand encryption
+    pub dealer_hand_nonce: u128,
+    /// Discarded cards, encrypted to the MXE only and packed like the deck
+    pub discard_pile: [[u8; 32]; DECK_LIMBS],
+    /// Cryptographic nonce for discard pile encryption
+    pub discard_pile_nonce: u128,
+    /// Salt of the deck commitment, encrypted to the MXE only until `reveal_deck`
+    pub deck_salt: [u8; 32],
+    /// Cryptographic nonce for deck salt encryption
+    pub deck_salt_nonce: u128,
+    /// Seats dealt from this deck, each with its own encrypted hand
+    pub seats: [Seat; MAX_SEATS],
+    /// House (dealer) seat of house-banked games, encrypted to the house key in
+    /// `player_enc_pubkey` rather than to any player
+    pub house: Seat,
+    /// Number of occupied seats
+    pub num_seats: u8,
     /// Revealed community cards (plaintext)
     pub community_cards: [u8; 5],
     /// Number of community cards revealed
     pub community_cards_size: u8,
+    /// Most community cards this game's board holds
+    pub max_community_cards: u8,
     /// Total number of cards dealt from the deck
-    pub cards_dealt: u8,
+    pub cards_dealt: u16,
+    /// Number of cards in the discard pile
+    pub discard_pile_size: u16,
+    /// Number of cards burned (skipped face down, never revealed)
+    pub cards_burned: u16,
     /// Unique identifier for this game session
     pub game_id: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Bitmask of the seats that won the last showdown
+    pub showdown_winners: u16,
+    /// Category of the winning showdown hand
+    /// (0 high card, 1 pair, 2 two pair, 3 trips, 4 straight,
+    /// 5 flush, 6 full house, 7 quads, 8 straight flush)
+    pub showdown_category: u8,
+    /// Number of cards in the dealer's hand
+    pub dealer_hand_size: u8,
+    /// Dealer's face-up card (plaintext)
+    pub dealer_upcard: u8,
+    /// Dealer's full hand, revealed once the dealer has played
+    pub dealer_cards: [u8; 11],
+    /// Dealer's final blackjack total
+    pub dealer_total: u8,
+    /// Whether the dealer has played out the current dealer hand
+    pub dealer_played: bool,
+    /// Blackjack result for each seat
+    /// (0 not playing, 1 lose, 2 push, 3 win, 4 blackjack)
+    pub blackjack_outcomes: [u8; MAX_SEATS],
+    /// Composition of the deck this game was shuffled from
+    pub deck_spec: DeckSpec,
+    /// Number of cards in the deck
+    pub deck_size: u16,
+    /// Whether the shuffle callback has populated the deck
+    pub deck_shuffled: bool,
+    /// SHA-256 of the commitment salt followed by the packed deck limbs (each as
+    /// 16 little-endian bytes), set by every shuffle of the deck. After a
+    /// reshuffle the hash is prefixed with `previous_deck_commitment` too
+    pub deck_commitment: [u8; 32],
+    /// Commitment the current one is chained to, if the deck has been reshuffled
+    pub previous_deck_commitment: Option<[u8; 32]>,
+    /// Whether `reveal_deck` has published the deck; no more cards can be dealt
+    pub deck_revealed: bool,
+    /// Computation queued on this game that has not called back yet
+    pub pending_computation: Option<PendingComputation>,
+    /// Account allowed to mutate the game (its creator or a delegated game program)
+    pub authority: Pubkey,
+    /// Notification the last callback recorded for a consumer program, waiting
+    /// for `deliver_notification`
+    pub pending_notification: Option<PendingNotification>,
+}
+
+impl CardGame {
+    /// Checks that a computation drawing `num_cards` from the deck can be queued
+    fn require_can_draw(&self, num_cards: u16) -> Result<()> {
+        require!(self.deck_shuffled, ErrorCode::DeckNotShuffled);
+        require!(!self.deck_revealed, ErrorCode::DeckRevealed);
+        self.require_idle()?;
+        require!(
+            self.cards_dealt + num_cards <= self.deck_size,
+            ErrorCode::DeckExhausted
+        );
+        Ok(())
+    }
+
+    /// Whether any seat, the house or an unplayed dealer hand still holds cards
+    /// dealt face down
+    fn holds_hidden_cards(&self) -> bool {
+        self.seats.iter().any(|seat| seat.hole_cards_size > 0)
+            || self.house.hole_cards_size > 0
+            || (self.dealer_hand_size > 0 && !self.dealer_played)
+    }
+
+    /// Checks that no computation is in flight on this game
+    fn require_idle(&self) -> Result<()> {
+        require!(
+            self.pending_computation.is_none(),
+            ErrorCode::ComputationPending
+        );
+        Ok(())
+    }
+
+    /// Records a queued computation. Its callback must call `complete_computation`
+    fn begin_computation(&mut self, offset: u64, kind: ComputationKind) -> Result<()> {
+        self.require_idle()?;
+        self.pending_computation = Some(PendingComputation {
+            offset,
+            kind,
+            queued_slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+
+    /// Checks that a callback is completing the computation this game is waiting
+    /// on, then clears it. Must run before the callback touches any state
+    fn complete_computation(
+        &mut self,
+        computation_account: Pubkey,
+        kind: ComputationKind,
+    ) -> Result<()> {
+        let pending = self
+            .pending_computation
+            .ok_or(ErrorCode::UnexpectedComputation)?;
+        require!(
+            pending.kind == kind && computation_account == derive_comp_pda!(pending.offset),
+            ErrorCode::UnexpectedComputation
+        );
+        self.pending_computation = None;
+        Ok(())
+    }
+}
+
+/// Baccarat coups played from a game's shoe; holds the result of the last one
+#[account]
+#[derive(InitSpace)]
+pub struct BaccaratRound {
+    /// Game whose shoe the coups are dealt from
+    pub game_id: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Number of coups played
+    pub coups_played: u32,
+    /// Player's cards (plaintext, empty card marker past the hand size)
+    pub player_cards: [u8; 3],
+    /// Number of Player cards (2 or 3)
+    pub player_cards_size: u8,
+    /// Banker's cards (plaintext, empty card marker past the hand size)
+    pub banker_cards: [u8; 3],
+    /// Number of Banker cards (2 or 3)
+    pub banker_cards_size: u8,
+    /// Final Player total (0-9)
+    pub player_total: u8,
+    /// Final Banker total (0-9)
+    pub banker_total: u8,
+    /// Outcome of the last coup (0 none yet, 1 Player wins, 2 Banker wins, 3 tie)
+    pub outcome: u8,
+}
+
+/// Computation queued on a game, awaiting its callback
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct PendingComputation {
+    /// Offset the computation was queued with
+    pub offset: u64,
+    pub kind: ComputationKind,
+    /// Slot the computation was queued in
+    pub queued_slot: u64,
+}
+
+/// Encrypted instruction a computation ran
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ComputationKind {
+    ShuffleAndDeal,
+    StoreHoleCards,
+    RevealCommunityCards,
+    ChangeHand,
+    SeatPlayer,
+    RevealHoleCards,
+    ResolveShowdown,
+    DealDealerHand,
+    PlayerHit,
+    DealerPlay,
+    ReshuffleRemaining,
+    DiscardAndDraw,
+    RevealDeck,
+    DealHouseCards,
+    RevealHouseCards,
+    PlayBaccarat,
+    PassCards,
+}
+
+/// Argument of the `on_card_shuffler_callback` instruction a consumer program
+/// receives when a computation it registered for completes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct CardShufflerNotification {
+    pub game_id: u64,
+    pub kind: ComputationKind,
+    /// False if the computation was aborted
+    pub success: bool,
+}
+
+/// Notification recorded by a callback, waiting for `deliver_notification`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct PendingNotification {
+    /// Program whose `on_card_shuffler_callback` receives the notification
+    pub consumer_program: Pubkey,
+    /// Hash of the accounts forwarded at queue time (see `forwarded_accounts_hash`)
+    pub accounts_hash: [u8; 32],
+    pub notification: CardShufflerNotification,
+}
+
+/// A player's contribution to a shuffle: a random u128 encrypted with the
+/// player's own x25519 key, so nobody else learns it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct EntropySeed {
+    /// x25519 public key the seed was encrypted with
+    pub enc_pubkey: [u8; 32],
+    pub nonce: u128,
+    pub ciphertext: [u8; 32],
+}
+
+/// Describes which cards make up a deck
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct DeckSpec {
+    /// Bit r includes rank r (0 = two ... 12 = ace)
+    pub rank_mask: u16,
+    /// Bit s includes suit s (0 clubs, 1 diamonds, 2 hearts, 3 spades)
+    pub suit_mask: u8,
+    /// Number of copies of the selected cards (1 for a single deck, 6 for a six-deck shoe)
+    pub copies: u8,
+    /// Number of jokers added to the deck (card index 52), at most two per copy
+    pub jokers: u8,
+}
+
+impl DeckSpec {
+    /// Standard 52-card deck
+    pub const STANDARD: DeckSpec = DeckSpec {
+        rank_mask: 0x1fff,
+        suit_mask: 0xf,
+        copies: 1,
+        jokers: 0,
+    };
+
+    /// Whether the spec describes a non-empty deck that fits in the packed representation
+    pub fn is_valid(&self) -> bool {
+        self.rank_mask != 0
+            && self.rank_mask >> 13 == 0
+            && self.suit_mask != 0
+            && self.suit_mask >> 4 == 0
+            && self.copies >= 1
+            && self.copies <= MAX_DECK_COPIES
+            && self.jokers <= 2 * self.copies
+    }
+
+    /// Number of cards in the deck
+    pub fn deck_size(&self) -> u16 {
+        (self.rank_mask.count_ones() * self.suit_mask.count_ones()) as u16 * self.copies as u16
+            + self.jokers as u16
+    }
+}
+
+/// A single player's position at the table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct Seat {
+    /// Player's encrypted hole cards
+    pub hole_cards: [u8; 32],
+    /// Cryptographic nonce for hole cards encryption
+    pub hole_cards_nonce: u128,
+    /// Number of hole cards currently held
+    pub hole_cards_size: u8,
     /// Solana public key of the player
     pub player_pubkey: Pubkey,
     /// Player's encryption public key for MPC operations
     pub player_enc_pubkey: [u8; 32],
-    /// PDA bump seed
-    pub bump: u8,
+    /// Hole cards revealed at showdown (plaintext)
+    pub revealed_cards: [u8; 11],
+    /// Number of hole cards revealed
+    pub revealed_cards_size: u8,
+    /// Whether the player has stood in the current blackjack round
+    pub stood: bool,
+    /// Whether the player's blackjack hand went over 21
+    pub busted: bool,
 }
 
 #[event]
 pub struct DeckShuffledEvent {
     pub game_id: u64,
+    pub deck_size: u16,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub num_hole_cards: u8,
+    pub deck_commitment: [u8; 32],
+}
+
+#[event]
+pub struct PlayerSeatedEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub player_pubkey: Pubkey,
     pub hole_cards: [u8; 32],
     pub hole_cards_nonce: u128,
     pub num_hole_cards: u8,
@@ -768,21 +4750,188 @@ pub struct DeckShuffledEvent {
 #[event]
 pub struct HoleCardsStoredEvent {
     pub game_id: u64,
+    pub seat: u8,
     pub hole_cards: [u8; 32],
     pub hole_cards_nonce: u128,
     pub total_hole_cards: u8,
+    pub num_burned: u8,
 }
 
 #[event]
 pub struct CommunityCardsRevealedEvent {
     pub game_id: u64,
+    /// Cards revealed by this computation (empty card marker past `num_revealed`)
+    pub new_cards: [u8; 5],
+    pub num_revealed: u8,
+    pub num_burned: u8,
+    /// Full board after the reveal
     pub community_cards: [u8; 5],
+    pub community_cards_size: u8,
+}
+
+#[event]
+pub struct HoleCardsRevealedEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub revealed_cards: [u8; 11],
+    pub num_revealed: u8,
+}
+
+#[event]
+pub struct ShowdownResolvedEvent {
+    pub game_id: u64,
+    pub winners: u16,
+    pub hand_category: u8,
+}
+
+#[event]
+pub struct DealerHandDealtEvent {
+    pub game_id: u64,
+    pub upcard: u8,
+}
+
+#[event]
+pub struct PlayerHitEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub total_hole_cards: u8,
+    pub busted: bool,
+}
+
+#[event]
+pub struct BoardClearedEvent {
+    pub game_id: u64,
+}
+
+#[event]
+pub struct PlayerStoodEvent {
+    pub game_id: u64,
+    pub seat: u8,
+}
+
+#[event]
+pub struct DealerPlayedEvent {
+    pub game_id: u64,
+    pub dealer_cards: [u8; 11],
+    pub dealer_total: u8,
+    pub outcomes: [u8; MAX_SEATS],
+}
+
+#[event]
+pub struct DeckReshuffledEvent {
+    pub game_id: u64,
+    pub cards_remaining: u16,
+    pub deck_commitment: [u8; 32],
+    /// Commitment `deck_commitment` is chained to
+    pub previous_deck_commitment: [u8; 32],
+}
+
+#[event]
+pub struct CardsDiscardedEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub num_discarded: u8,
+    pub discard_pile_size: u16,
+}
+
+#[event]
+pub struct DeckRevealedEvent {
+    pub game_id: u64,
+    /// Packed deck limbs (21 cards per limb, six bits each, first card lowest)
+    pub deck: [u128; DECK_LIMBS],
+    pub deck_salt: u128,
+    pub deck_commitment: [u8; 32],
+    /// Commitment `deck_commitment` is chained to, if the deck was reshuffled
+    pub previous_deck_commitment: Option<[u8; 32]>,
+    pub deck_size: u16,
+    pub cards_dealt: u16,
+}
+
+#[event]
+pub struct HouseKeySetEvent {
+    pub game_id: u64,
+    pub house_enc_pubkey: [u8; 32],
+}
+
+#[event]
+pub struct HouseCardsDealtEvent {
+    pub game_id: u64,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub total_hole_cards: u8,
+    pub num_burned: u8,
+}
+
+#[event]
+pub struct HouseCardsRevealedEvent {
+    pub game_id: u64,
+    pub revealed_cards: [u8; 11],
     pub num_revealed: u8,
 }
 
+#[event]
+pub struct BaccaratPlayedEvent {
+    pub game_id: u64,
+    pub coup: u32,
+    pub player_cards: [u8; 3],
+    pub player_cards_size: u8,
+    pub banker_cards: [u8; 3],
+    pub banker_cards_size: u8,
+    pub player_total: u8,
+    pub banker_total: u8,
+    pub outcome: u8,
+}
+
+#[event]
+pub struct CardsPassedEvent {
+    pub game_id: u64,
+    pub sender_seat: u8,
+    pub recipient_seat: u8,
+    pub sender_hole_cards: [u8; 32],
+    pub sender_hole_cards_nonce: u128,
+    pub recipient_hole_cards: [u8; 32],
+    pub recipient_hole_cards_nonce: u128,
+    pub num_passed: u8,
+}
+
+#[event]
+pub struct ComputationAbortedEvent {
+    pub game_id: u64,
+}
+
+#[event]
+pub struct ComputationCancelledEvent {
+    pub game_id: u64,
+    pub offset: u64,
+    pub kind: ComputationKind,
+}
+
+#[event]
+pub struct AuthorityTransferredEvent {
+    pub game_id: u64,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct CardGameClosedEvent {
+    pub game_id: u64,
+    pub destination: Pubkey,
+    pub lamports: u64,
+    pub num_seats: u8,
+    pub cards_dealt: u16,
+    pub deck_size: u16,
+    pub community_cards_size: u8,
+}
+
 #[event]
 pub struct HandChangedEvent {
     pub game_id: u64,
+    pub seat: u8,
     pub new_hand: [u8; 32],
     pub new_nonce: u128,
 }
@@ -793,4 +4942,64 @@ pub enum ErrorCode {
     AbortedComputation,
     #[msg("Cluster not set")]
     ClusterNotSet,
+    #[msg("Seat is not occupied")]
+    InvalidSeat,
+    #[msg("All seats are taken")]
+    TableFull,
+    #[msg("All five community cards must be revealed")]
+    BoardIncomplete,
+    #[msg("A contending seat holds fewer than two hole cards")]
+    NotEnoughHoleCards,
+    #[msg("Seat has already stood or busted")]
+    SeatFinished,
+    #[msg("The dealer has not been dealt a hand")]
+    DealerHandNotDealt,
+    #[msg("The dealer has already played this hand")]
+    DealerAlreadyPlayed,
+    #[msg("The dealer's current hand has not been played yet")]
+    DealerHandInPlay,
+    #[msg("Some seats have not stood or busted yet")]
+    PlayersStillActing,
+    #[msg("Invalid deck specification")]
+    InvalidDeckSpec,
+    #[msg("The deck has not been shuffled yet")]
+    DeckNotShuffled,
+    #[msg("Not enough cards left in the deck")]
+    DeckExhausted,
+    #[msg("The hand cannot hold that many cards")]
+    HandFull,
+    #[msg("Discard mask must select at least one card in the hand")]
+    InvalidDiscard,
+    #[msg("Too many cards requested for reveal")]
+    RevealLimitExceeded,
+    #[msg("Board cannot hold more than 5 community cards")]
+    InvalidBoardSize,
+    #[msg("Another computation for this game has not completed")]
+    ComputationPending,
+    #[msg("Callback does not match the pending computation")]
+    UnexpectedComputation,
+    #[msg("Signer is not the game authority")]
+    Unauthorized,
+    #[msg("The first remaining account must be an executable consumer program")]
+    InvalidConsumerProgram,
+    #[msg("Notifier account does not match the card_shuffler notifier PDA")]
+    InvalidNotifier,
+    #[msg("The deck has been revealed")]
+    DeckRevealed,
+    #[msg("Too many entropy seeds for one shuffle")]
+    TooManyEntropySeeds,
+    #[msg("The house seat has no encryption key")]
+    HouseKeyNotSet,
+    #[msg("No computation is pending on this game")]
+    NoPendingComputation,
+    #[msg("The pending computation has not timed out yet")]
+    ComputationNotTimedOut,
+    #[msg("Pass must move at least one card and no more than the sender holds")]
+    InvalidPass,
+    #[msg("Hands still hold cards; the deck can only be revealed once every hand is empty")]
+    HiddenCardsInPlay,
+    #[msg("No notification is waiting for delivery")]
+    NoPendingNotification,
+    #[msg("Forwarded accounts do not match the ones registered for the notification")]
+    ForwardedAccountsMismatch,
 }
@@ -0,0 +1,35 @@
+// Prints the known-answer vectors checked by card_shuffler_client's crypto tests,
+// computed with Arcium's TypeScript client. Run from arcium_jobs/card_shuffler:
+//
+//   node scripts/crypto_vectors.mjs
+import { RescueCipher, x25519 } from "@arcium-hq/client";
+
+const hex = (bytes) => Buffer.from(bytes).toString("hex");
+
+// RFC 7748, section 6.1
+const alice = Buffer.from(
+  "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+  "hex"
+);
+const bob = Buffer.from(
+  "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+  "hex"
+);
+const sharedSecret = x25519.getSharedSecret(alice, x25519.getPublicKey(bob));
+console.log("public key:   ", hex(x25519.getPublicKey(alice)));
+console.log("shared secret:", hex(sharedSecret));
+
+// As, Kd and nine empty slots, packed like the circuits' Hand
+const hand = [51, 24, ...Array(9).fill(53)];
+const packedHand = hand.reduce(
+  (packed, card, slot) => packed | (BigInt(card) << BigInt(6 * slot)),
+  0n
+);
+
+// Nonce 0x0102...10 as a little-endian u128
+const nonce = Buffer.from("100f0e0d0c0b0a090807060504030201", "hex");
+const plaintexts = [packedHand, 0n, 1n, (1n << 128n) - 1n, 42n, 7n];
+const cipher = new RescueCipher(sharedSecret);
+for (const ciphertext of cipher.encrypt(plaintexts, nonce)) {
+  console.log(hex(ciphertext));
+}
@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey, Keypair } from "@solana/web3.js";
 import { CardShuffler } from "../target/types/card_shuffler";
-import { randomBytes } from "crypto";
+import { randomBytes, createHash } from "crypto";
 import {
   awaitComputationFinalization,
   getArciumEnv,
@@ -94,6 +94,45 @@ describe("CardShuffler", () => {
       initChangeHandCompDef(program as any, owner, false, false).then((sig) =>
         console.log("Change Hand CompDef Init Sig:", sig)
       ),
+      initSeatPlayerCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Seat Player CompDef Init Sig:", sig)
+      ),
+      initRevealHoleCardsCompDef(program as any, owner, false, false).then(
+        (sig) => console.log("Reveal Hole Cards CompDef Init Sig:", sig)
+      ),
+      initResolveShowdownCompDef(program as any, owner, false, false).then(
+        (sig) => console.log("Resolve Showdown CompDef Init Sig:", sig)
+      ),
+      initDealDealerHandCompDef(program as any, owner, false, false).then(
+        (sig) => console.log("Deal Dealer Hand CompDef Init Sig:", sig)
+      ),
+      initPlayerHitCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Player Hit CompDef Init Sig:", sig)
+      ),
+      initDealerPlayCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Dealer Play CompDef Init Sig:", sig)
+      ),
+      initReshuffleRemainingCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Reshuffle Remaining CompDef Init Sig:", sig)
+      ),
+      initDiscardAndDrawCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Discard And Draw CompDef Init Sig:", sig)
+      ),
+      initRevealDeckCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Reveal Deck CompDef Init Sig:", sig)
+      ),
+      initDealHouseCardsCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Deal House Cards CompDef Init Sig:", sig)
+      ),
+      initRevealHouseCardsCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Reveal House Cards CompDef Init Sig:", sig)
+      ),
+      initPlayBaccaratCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Play baccarat CompDef Init Sig:", sig)
+      ),
+      initPassCardsCompDef(program as any, owner, false, false).then((sig) =>
+        console.log("Pass cards CompDef Init Sig:", sig)
+      ),
     ]);
     console.log("All computation definitions initialized.");
     await new Promise((res) => setTimeout(res, 2000));
@@ -112,6 +151,14 @@ describe("CardShuffler", () => {
 
     const gameId = BigInt(Math.floor(Math.random() * 1000000));
     const mxeNonce = randomBytes(16);
+    const saltNonce = randomBytes(16);
+    // The creator contributes a seed to the shuffle, encrypted with their key
+    const seedNonce = randomBytes(16);
+    const entropySeed = {
+      encPubkey: Array.from(publicKey),
+      nonce: new anchor.BN(deserializeLE(seedNonce).toString()),
+      ciphertext: cipher.encrypt([deserializeLE(randomBytes(16))], seedNonce)[0],
+    };
 
     const computationOffsetInit = new anchor.BN(randomBytes(8));
 
@@ -133,9 +180,14 @@ describe("CardShuffler", () => {
         computationOffsetInit,
         new anchor.BN(gameId.toString()),
         new anchor.BN(deserializeLE(mxeNonce).toString()),
+        new anchor.BN(deserializeLE(saltNonce).toString()),
         Array.from(publicKey),
         new anchor.BN(deserializeLE(clientNonce).toString()),
-        2 // Deal 2 initial hole cards
+        2, // Deal 2 initial hole cards
+        // Standard 52-card deck
+        { rankMask: 0x1fff, suitMask: 0xf, copies: 1, jokers: 0 },
+        5, // Hold'em board
+        [entropySeed]
       )
       .accountsPartial({
         computationAccount: getComputationAccAddress(
@@ -153,6 +205,7 @@ describe("CardShuffler", () => {
           ).readUInt32LE()
         ),
         cardGame: cardGamePDA,
+        authority: owner.publicKey,
       })
       .signers([owner])
       .rpc({ commitment: "confirmed" });
@@ -172,8 +225,10 @@ describe("CardShuffler", () => {
 
     const deckShuffledEvent = await deckShuffledEventPromise;
     console.log("Received DeckShuffledEvent.");
+    expect(deckShuffledEvent.deckSize).to.equal(52);
 
     let gameState = await program.account.cardGame.fetch(cardGamePDA);
+    expect(gameState.authority.toBase58()).to.equal(owner.publicKey.toBase58());
 
     let currentClientNonce = Uint8Array.from(
       deckShuffledEvent.holeCardsNonce.toArray("le", 16)
@@ -186,15 +241,78 @@ describe("CardShuffler", () => {
     );
     let holeCards = decompressHand(
       compressedHoleCards[0],
-      gameState.holeCardsSize
+      gameState.seats[0].holeCardsSize
     );
     console.log(
       `Initial Hole Cards: ${holeCards.join(", ")} (${
-        gameState.holeCardsSize
+        gameState.seats[0].holeCardsSize
       } cards)`
     );
 
     expect(holeCards.length).to.equal(2);
+    const initialHoleCards = [...holeCards];
+    expect(deckShuffledEvent.deckCommitment).to.have.length(32);
+
+    console.log("\n--- Seating a second player ---");
+    const secondPrivateKey = x25519.utils.randomSecretKey();
+    const secondPublicKey = x25519.getPublicKey(secondPrivateKey);
+    const secondCipher = new RescueCipher(
+      x25519.getSharedSecret(secondPrivateKey, mxePublicKey)
+    );
+    const secondClientNonce = randomBytes(16);
+    const seatPlayerComputationOffset = new anchor.BN(randomBytes(8));
+    const playerSeatedEventPromise = awaitEvent("playerSeatedEvent");
+
+    const seatPlayerSig = await program.methods
+      .seatPlayer(
+        seatPlayerComputationOffset,
+        new anchor.BN(gameId.toString()),
+        Keypair.generate().publicKey,
+        Array.from(secondPublicKey),
+        new anchor.BN(deserializeLE(secondClientNonce).toString()),
+        2
+      )
+      .accountsPartial({
+        computationAccount: getComputationAccAddress(
+          program.programId,
+          seatPlayerComputationOffset
+        ),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("seat_player")).readUInt32LE()
+        ),
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+        payer: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    console.log("Seat Player TX Signature:", seatPlayerSig);
+
+    await awaitComputationFinalization(
+      provider,
+      seatPlayerComputationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const playerSeatedEvent = await playerSeatedEventPromise;
+    expect(playerSeatedEvent.seat).to.equal(1);
+
+    const secondHoleCards = decompressHand(
+      secondCipher.decrypt(
+        [playerSeatedEvent.holeCards],
+        Uint8Array.from(playerSeatedEvent.holeCardsNonce.toArray("le", 16))
+      )[0],
+      playerSeatedEvent.numHoleCards
+    );
+    console.log(`Second Seat Hole Cards: ${secondHoleCards.join(", ")}`);
+
+    expect(secondHoleCards.length).to.equal(2);
+    secondHoleCards.forEach((card) => expect(holeCards).to.not.include(card));
 
     console.log("\n--- Storing additional hole cards ---");
     const storeHoleCardsComputationOffset = new anchor.BN(randomBytes(8));
@@ -204,7 +322,9 @@ describe("CardShuffler", () => {
       .storeHoleCards(
         storeHoleCardsComputationOffset,
         new anchor.BN(gameId.toString()),
-        1 // Add 1 more card
+        0, // Seat 0 (the creator)
+        1, // Add 1 more card
+        0 // No burn
       )
       .accountsPartial({
         computationAccount: getComputationAccAddress(
@@ -220,6 +340,7 @@ describe("CardShuffler", () => {
           Buffer.from(getCompDefAccOffset("store_hole_cards")).readUInt32LE()
         ),
         cardGame: cardGamePDA,
+        authority: owner.publicKey,
         payer: owner.publicKey,
       })
       .signers([owner])
@@ -249,15 +370,68 @@ describe("CardShuffler", () => {
       [holeCardsStoredEvent.holeCards],
       currentClientNonce
     );
-    holeCards = decompressHand(compressedHoleCards[0], gameState.holeCardsSize);
+    holeCards = decompressHand(
+      compressedHoleCards[0],
+      gameState.seats[0].holeCardsSize
+    );
     console.log(
       `Updated Hole Cards: ${holeCards.join(", ")} (${
-        gameState.holeCardsSize
+        gameState.seats[0].holeCardsSize
       } cards)`
     );
 
     expect(holeCards.length).to.equal(3);
 
+    console.log("\n--- Revealing the second seat's hole cards ---");
+    const revealHoleCardsComputationOffset = new anchor.BN(randomBytes(8));
+    const holeCardsRevealedEventPromise = awaitEvent("holeCardsRevealedEvent");
+
+    const revealHoleCardsSig = await program.methods
+      .revealHoleCards(
+        revealHoleCardsComputationOffset,
+        new anchor.BN(gameId.toString()),
+        1, // Seat 1
+        0b11 // Reveal both hole cards
+      )
+      .accountsPartial({
+        computationAccount: getComputationAccAddress(
+          program.programId,
+          revealHoleCardsComputationOffset
+        ),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("reveal_hole_cards")).readUInt32LE()
+        ),
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+        payer: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    console.log("Reveal Hole Cards TX Signature:", revealHoleCardsSig);
+
+    await awaitComputationFinalization(
+      provider,
+      revealHoleCardsComputationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const holeCardsRevealedEvent = await holeCardsRevealedEventPromise;
+    const revealedHoleCards = holeCardsRevealedEvent.revealedCards.slice(
+      0,
+      holeCardsRevealedEvent.numRevealed
+    );
+    console.log(`Revealed Seat 1 Hole Cards: ${revealedHoleCards.join(", ")}`);
+
+    expect(holeCardsRevealedEvent.numRevealed).to.equal(2);
+    expect([...revealedHoleCards].sort()).to.deep.equal(
+      [...secondHoleCards].sort()
+    );
+
     console.log("\n--- Revealing community cards ---");
     const revealCommunityComputationOffset = new anchor.BN(randomBytes(8));
     const communityCardsRevealedEventPromise = awaitEvent(
@@ -268,7 +442,8 @@ describe("CardShuffler", () => {
       .revealCommunityCards(
         revealCommunityComputationOffset,
         new anchor.BN(gameId.toString()),
-        3 // Reveal 3 community cards (the flop)
+        3, // Reveal 3 community cards (the flop)
+        1 // Burn 1 card first
       )
       .accountsPartial({
         computationAccount: getComputationAccAddress(
@@ -286,6 +461,7 @@ describe("CardShuffler", () => {
           ).readUInt32LE()
         ),
         cardGame: cardGamePDA,
+        authority: owner.publicKey,
         payer: owner.publicKey,
       })
       .signers([owner])
@@ -308,18 +484,21 @@ describe("CardShuffler", () => {
       await communityCardsRevealedEventPromise;
     console.log("Received CommunityCardsRevealedEvent.");
 
-    const communityCards = communityCardsRevealedEvent.communityCards;
+    const newCards = communityCardsRevealedEvent.newCards;
     const numRevealed = communityCardsRevealedEvent.numRevealed;
     console.log(
-      `Community Cards: ${communityCards
+      `Community Cards: ${newCards
         .slice(0, numRevealed)
         .join(", ")} (${numRevealed} cards)`
     );
+    expect(communityCardsRevealedEvent.communityCardsSize).to.equal(3);
 
     expect(numRevealed).to.equal(3);
+    expect(communityCardsRevealedEvent.numBurned).to.equal(1);
 
     gameState = await program.account.cardGame.fetch(cardGamePDA);
     expect(gameState.communityCardsSize).to.equal(3);
+    expect(gameState.cardsBurned).to.equal(1);
 
     console.log("\n--- Changing hand for new round ---");
     const changeHandComputationOffset = new anchor.BN(randomBytes(8));
@@ -331,6 +510,7 @@ describe("CardShuffler", () => {
       .changeHand(
         changeHandComputationOffset,
         new anchor.BN(gameId.toString()),
+        0,
         new anchor.BN(deserializeLE(newNonce).toString())
       )
       .accountsPartial({
@@ -347,6 +527,7 @@ describe("CardShuffler", () => {
           Buffer.from(getCompDefAccOffset("change_hand")).readUInt32LE()
         ),
         cardGame: cardGamePDA,
+        authority: owner.publicKey,
         payer: owner.publicKey,
       })
       .signers([owner])
@@ -369,8 +550,537 @@ describe("CardShuffler", () => {
     console.log("Received HandChangedEvent.");
 
     gameState = await program.account.cardGame.fetch(cardGamePDA);
-    expect(gameState.holeCardsSize).to.equal(0);
+    expect(gameState.seats[0].holeCardsSize).to.equal(0);
     console.log("Hand successfully reset for new round!");
+
+    console.log("\n--- Passing a card between seats ---");
+    const passCardsComputationOffset = new anchor.BN(randomBytes(8));
+    const cardsPassedEventPromise = awaitEvent("cardsPassedEvent");
+    // The second player picks slot 0, encrypted with their own key
+    const selectionNonce = randomBytes(16);
+    const selectionCiphertext = secondCipher.encrypt(
+      [BigInt(0b01)],
+      selectionNonce
+    )[0];
+
+    await program.methods
+      .passCards(
+        passCardsComputationOffset,
+        new anchor.BN(gameId.toString()),
+        1, // From the second player
+        0, // To the creator's emptied hand
+        1,
+        new anchor.BN(deserializeLE(selectionNonce).toString()),
+        selectionCiphertext
+      )
+      .accountsPartial({
+        computationAccount: getComputationAccAddress(
+          program.programId,
+          passCardsComputationOffset
+        ),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("pass_cards")).readUInt32LE()
+        ),
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+        payer: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    await awaitComputationFinalization(
+      provider,
+      passCardsComputationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const cardsPassedEvent = await cardsPassedEventPromise;
+    expect(cardsPassedEvent.numPassed).to.equal(1);
+
+    const keptCards = decompressHand(
+      secondCipher.decrypt(
+        [cardsPassedEvent.senderHoleCards],
+        Uint8Array.from(cardsPassedEvent.senderHoleCardsNonce.toArray("le", 16))
+      )[0],
+      1
+    );
+    const receivedCards = decompressHand(
+      cipher.decrypt(
+        [cardsPassedEvent.recipientHoleCards],
+        Uint8Array.from(
+          cardsPassedEvent.recipientHoleCardsNonce.toArray("le", 16)
+        )
+      )[0],
+      1
+    );
+    console.log(`Passed ${receivedCards[0]}, kept ${keptCards[0]}`);
+    expect([...receivedCards, ...keptCards].sort()).to.deep.equal(
+      [...secondHoleCards].sort()
+    );
+
+    gameState = await program.account.cardGame.fetch(cardGamePDA);
+    expect(gameState.seats[0].holeCardsSize).to.equal(1);
+    expect(gameState.seats[1].holeCardsSize).to.equal(1);
+    console.log("Card passed!");
+
+    console.log("\n--- Dealing to the house seat ---");
+    const housePrivateKey = x25519.utils.randomSecretKey();
+    const housePublicKey = x25519.getPublicKey(housePrivateKey);
+    const houseCipher = new RescueCipher(
+      x25519.getSharedSecret(housePrivateKey, mxePublicKey)
+    );
+
+    await program.methods
+      .setHouseKey(new anchor.BN(gameId.toString()), Array.from(housePublicKey))
+      .accountsPartial({
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    const dealHouseComputationOffset = new anchor.BN(randomBytes(8));
+    const houseCardsDealtEventPromise = awaitEvent("houseCardsDealtEvent");
+    const houseNonce = randomBytes(16);
+
+    await program.methods
+      .dealHouseCards(
+        dealHouseComputationOffset,
+        new anchor.BN(gameId.toString()),
+        new anchor.BN(deserializeLE(houseNonce).toString()),
+        2, // Two cards for the house
+        0 // No burn
+      )
+      .accountsPartial({
+        computationAccount: getComputationAccAddress(
+          program.programId,
+          dealHouseComputationOffset
+        ),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("deal_house_cards")).readUInt32LE()
+        ),
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+        payer: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    await awaitComputationFinalization(
+      provider,
+      dealHouseComputationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const houseCardsDealtEvent = await houseCardsDealtEventPromise;
+    expect(houseCardsDealtEvent.totalHoleCards).to.equal(2);
+
+    // Only the house key can read the house hand
+    const houseCards = decompressHand(
+      houseCipher.decrypt(
+        [houseCardsDealtEvent.holeCards],
+        Uint8Array.from(houseCardsDealtEvent.holeCardsNonce.toArray("le", 16))
+      )[0],
+      2
+    );
+    console.log("House cards:", houseCards);
+
+    const revealHouseComputationOffset = new anchor.BN(randomBytes(8));
+    const houseCardsRevealedEventPromise = awaitEvent(
+      "houseCardsRevealedEvent"
+    );
+
+    await program.methods
+      .revealHouseCards(
+        revealHouseComputationOffset,
+        new anchor.BN(gameId.toString()),
+        0b11 // Both cards
+      )
+      .accountsPartial({
+        computationAccount: getComputationAccAddress(
+          program.programId,
+          revealHouseComputationOffset
+        ),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("reveal_house_cards")).readUInt32LE()
+        ),
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+        payer: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    await awaitComputationFinalization(
+      provider,
+      revealHouseComputationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const houseCardsRevealedEvent = await houseCardsRevealedEventPromise;
+    expect(houseCardsRevealedEvent.numRevealed).to.equal(2);
+    // decompressHand lists the cards last dealt first
+    expect(houseCardsRevealedEvent.revealedCards.slice(0, 2)).to.deep.equal(
+      [...houseCards].reverse()
+    );
+    console.log("House hand revealed!");
+
+    console.log("\n--- Playing a baccarat coup ---");
+    const baccaratComputationOffset = new anchor.BN(randomBytes(8));
+    const baccaratPlayedEventPromise = awaitEvent("baccaratPlayedEvent");
+    const baccaratRoundPDA = PublicKey.findProgramAddressSync(
+      [Buffer.from("baccarat"), gameIdBuffer],
+      program.programId
+    )[0];
+    const cardsDealtBeforeCoup = (
+      await program.account.cardGame.fetch(cardGamePDA)
+    ).cardsDealt;
+
+    await program.methods
+      .playBaccarat(baccaratComputationOffset, new anchor.BN(gameId.toString()))
+      .accountsPartial({
+        computationAccount: getComputationAccAddress(
+          program.programId,
+          baccaratComputationOffset
+        ),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("play_baccarat")).readUInt32LE()
+        ),
+        cardGame: cardGamePDA,
+        baccaratRound: baccaratRoundPDA,
+        authority: owner.publicKey,
+        payer: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    await awaitComputationFinalization(
+      provider,
+      baccaratComputationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const baccaratPlayedEvent = await baccaratPlayedEventPromise;
+    console.log("Baccarat coup:", baccaratPlayedEvent);
+
+    // 2-9 count face value, tens and faces zero, aces one
+    const baccaratTotal = (cards: number[]) =>
+      cards.reduce((total, card) => {
+        const rank = card % 13;
+        const value = rank === 12 ? 1 : rank >= 8 ? 0 : rank + 2;
+        return (total + value) % 10;
+      }, 0);
+    const playerTotal = baccaratTotal(
+      baccaratPlayedEvent.playerCards.slice(0, baccaratPlayedEvent.playerCardsSize)
+    );
+    const bankerTotal = baccaratTotal(
+      baccaratPlayedEvent.bankerCards.slice(0, baccaratPlayedEvent.bankerCardsSize)
+    );
+    expect(baccaratPlayedEvent.playerTotal).to.equal(playerTotal);
+    expect(baccaratPlayedEvent.bankerTotal).to.equal(bankerTotal);
+    expect(baccaratPlayedEvent.outcome).to.equal(
+      playerTotal > bankerTotal ? 1 : bankerTotal > playerTotal ? 2 : 3
+    );
+
+    const baccaratRound = await program.account.baccaratRound.fetch(
+      baccaratRoundPDA
+    );
+    expect(baccaratRound.coupsPlayed).to.equal(1);
+    expect(baccaratRound.outcome).to.equal(baccaratPlayedEvent.outcome);
+    gameState = await program.account.cardGame.fetch(cardGamePDA);
+    expect(gameState.cardsDealt).to.equal(
+      cardsDealtBeforeCoup +
+        baccaratPlayedEvent.playerCardsSize +
+        baccaratPlayedEvent.bankerCardsSize
+    );
+
+    await program.methods
+      .closeBaccaratRound(new anchor.BN(gameId.toString()))
+      .accountsPartial({
+        cardGame: cardGamePDA,
+        baccaratRound: baccaratRoundPDA,
+        authority: owner.publicKey,
+        destination: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    console.log("Baccarat round closed!");
+
+    console.log("\n--- Reshuffling with a consumer that fails ---");
+    // The system program rejects the notification, standing in for a consumer
+    // program that returns an error
+    const failingConsumer = {
+      pubkey: anchor.web3.SystemProgram.programId,
+      isSigner: false,
+      isWritable: false,
+    };
+    const commitmentBeforeReshuffle = (
+      await program.account.cardGame.fetch(cardGamePDA)
+    ).deckCommitment;
+    const reshuffleComputationOffset = new anchor.BN(randomBytes(8));
+    const deckReshuffledEventPromise = awaitEvent("deckReshuffledEvent");
+
+    await program.methods
+      .reshuffleRemaining(
+        reshuffleComputationOffset,
+        new anchor.BN(gameId.toString()),
+        []
+      )
+      .accountsPartial({
+        computationAccount: getComputationAccAddress(
+          program.programId,
+          reshuffleComputationOffset
+        ),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("reshuffle_remaining")).readUInt32LE()
+        ),
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+        payer: owner.publicKey,
+      })
+      .remainingAccounts([failingConsumer])
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    await awaitComputationFinalization(
+      provider,
+      reshuffleComputationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const deckReshuffledEvent = await deckReshuffledEventPromise;
+    expect(Array.from(deckReshuffledEvent.previousDeckCommitment)).to.deep.equal(
+      Array.from(commitmentBeforeReshuffle)
+    );
+
+    // The callback committed the reshuffle and only recorded the notification
+    gameState = await program.account.cardGame.fetch(cardGamePDA);
+    expect(gameState.pendingComputation).to.be.null;
+    expect(Array.from(gameState.deckCommitment)).to.deep.equal(
+      Array.from(deckReshuffledEvent.deckCommitment)
+    );
+    expect(gameState.pendingNotification.consumerProgram.toBase58()).to.equal(
+      failingConsumer.pubkey.toBase58()
+    );
+    expect(gameState.pendingNotification.notification.kind).to.deep.equal({
+      reshuffleRemaining: {},
+    });
+    expect(gameState.pendingNotification.notification.success).to.equal(true);
+
+    // Delivering it fails on its own, leaving the reshuffle and the
+    // notification in place
+    const delivered = await program.methods
+      .deliverNotification(new anchor.BN(gameId.toString()))
+      .accountsPartial({
+        cardGame: cardGamePDA,
+        notifier: PublicKey.findProgramAddressSync(
+          [Buffer.from("notifier")],
+          program.programId
+        )[0],
+      })
+      .remainingAccounts([failingConsumer])
+      .rpc({ commitment: "confirmed" })
+      .then(
+        () => true,
+        () => false
+      );
+    expect(delivered).to.equal(false);
+    gameState = await program.account.cardGame.fetch(cardGamePDA);
+    expect(gameState.pendingNotification).to.not.be.null;
+    expect(Array.from(gameState.deckCommitment)).to.deep.equal(
+      Array.from(deckReshuffledEvent.deckCommitment)
+    );
+    console.log("Reshuffle kept despite the failing consumer!");
+
+    console.log("\n--- Revealing the deck ---");
+    const revealDeckAccounts = (computationOffset: anchor.BN) => ({
+      computationAccount: getComputationAccAddress(
+        program.programId,
+        computationOffset
+      ),
+      clusterAccount: arciumEnv.arciumClusterPubkey,
+      mxeAccount: getMXEAccAddress(program.programId),
+      mempoolAccount: getMempoolAccAddress(program.programId),
+      executingPool: getExecutingPoolAccAddress(program.programId),
+      compDefAccount: getCompDefAccAddress(
+        program.programId,
+        Buffer.from(getCompDefAccOffset("reveal_deck")).readUInt32LE()
+      ),
+      cardGame: cardGamePDA,
+      authority: owner.publicKey,
+      payer: owner.publicKey,
+    });
+
+    // The deck would show the cards still in play
+    const earlyRevealOffset = new anchor.BN(randomBytes(8));
+    try {
+      await program.methods
+        .revealDeck(earlyRevealOffset, new anchor.BN(gameId.toString()))
+        .accountsPartial(revealDeckAccounts(earlyRevealOffset))
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("revealDeck should fail while hands hold cards");
+    } catch (error) {
+      expect((error as anchor.AnchorError).error.errorCode.code).to.equal(
+        "HiddenCardsInPlay"
+      );
+    }
+
+    // Empty every hand: both seats, then the house
+    for (const seat of [0, 1]) {
+      const emptyHandComputationOffset = new anchor.BN(randomBytes(8));
+      await program.methods
+        .changeHand(
+          emptyHandComputationOffset,
+          new anchor.BN(gameId.toString()),
+          seat,
+          new anchor.BN(deserializeLE(randomBytes(16)).toString())
+        )
+        .accountsPartial({
+          computationAccount: getComputationAccAddress(
+            program.programId,
+            emptyHandComputationOffset
+          ),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("change_hand")).readUInt32LE()
+          ),
+          cardGame: cardGamePDA,
+          authority: owner.publicKey,
+          payer: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+      await awaitComputationFinalization(
+        provider,
+        emptyHandComputationOffset,
+        program.programId,
+        "confirmed"
+      );
+    }
+    await program.methods
+      .setHouseKey(new anchor.BN(gameId.toString()), Array.from(housePublicKey))
+      .accountsPartial({
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    gameState = await program.account.cardGame.fetch(cardGamePDA);
+    expect(gameState.seats[0].holeCardsSize).to.equal(0);
+    expect(gameState.seats[1].holeCardsSize).to.equal(0);
+    expect(gameState.house.holeCardsSize).to.equal(0);
+
+    const revealDeckComputationOffset = new anchor.BN(randomBytes(8));
+    const deckRevealedEventPromise = awaitEvent("deckRevealedEvent");
+
+    const revealDeckSig = await program.methods
+      .revealDeck(revealDeckComputationOffset, new anchor.BN(gameId.toString()))
+      .accountsPartial(revealDeckAccounts(revealDeckComputationOffset))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    console.log("Reveal Deck TX Signature:", revealDeckSig);
+
+    await awaitComputationFinalization(
+      provider,
+      revealDeckComputationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const deckRevealedEvent = await deckRevealedEventPromise;
+
+    // The reshuffle chained the commitment to the one before it:
+    // sha256(previous || salt || deck limbs), salt and limbs as 16
+    // little-endian bytes each
+    expect(Array.from(deckRevealedEvent.previousDeckCommitment)).to.deep.equal(
+      Array.from(commitmentBeforeReshuffle)
+    );
+    expect(Array.from(deckRevealedEvent.deckCommitment)).to.deep.equal(
+      Array.from(deckReshuffledEvent.deckCommitment)
+    );
+    const commitment = createHash("sha256")
+      .update(Buffer.from(deckRevealedEvent.previousDeckCommitment))
+      .update(deckRevealedEvent.deckSalt.toArrayLike(Buffer, "le", 16))
+      .update(
+        Buffer.concat(
+          deckRevealedEvent.deck.map((limb) => limb.toArrayLike(Buffer, "le", 16))
+        )
+      )
+      .digest();
+    expect(Array.from(commitment)).to.deep.equal(
+      Array.from(deckRevealedEvent.deckCommitment)
+    );
+
+    const revealedDeck: number[] = [];
+    for (const limb of deckRevealedEvent.deck) {
+      let value = BigInt(limb.toString());
+      for (let i = 0; i < 21; i++) {
+        revealedDeck.push(Number(value % BigInt(64)));
+        value >>= BigInt(6);
+      }
+    }
+    const dealtCards = revealedDeck.slice(0, deckRevealedEvent.cardsDealt);
+    expect(dealtCards.slice(0, 2)).to.deep.equal(initialHoleCards);
+    gameState = await program.account.cardGame.fetch(cardGamePDA);
+    expect(gameState.deckRevealed).to.equal(true);
+    for (const card of gameState.communityCards.slice(
+      0,
+      gameState.communityCardsSize
+    )) {
+      expect(dealtCards).to.include(card);
+    }
+    console.log("Deck matches its commitment!");
+
+    console.log("\n--- Closing card game ---");
+    const cardGameClosedEventPromise = awaitEvent("cardGameClosedEvent");
+    await program.methods
+      .closeCardGame(new anchor.BN(gameId.toString()))
+      .accountsPartial({
+        cardGame: cardGamePDA,
+        authority: owner.publicKey,
+        destination: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    const cardGameClosedEvent = await cardGameClosedEventPromise;
+    expect(cardGameClosedEvent.destination.toBase58()).to.equal(
+      owner.publicKey.toBase58()
+    );
+    expect(
+      await provider.connection.getAccountInfo(cardGamePDA, "confirmed")
+    ).to.equal(null);
+    console.log("Card game closed and rent reclaimed!");
   });
 
   // helpres
@@ -434,6 +1144,201 @@ describe("CardShuffler", () => {
     );
   }
 
+  async function initSeatPlayerCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "seat_player",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initRevealHoleCardsCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "reveal_hole_cards",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initResolveShowdownCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "resolve_showdown",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initDealDealerHandCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "deal_dealer_hand",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initPlayerHitCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "player_hit",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initDealerPlayCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "dealer_play",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initReshuffleRemainingCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "reshuffle_remaining",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initDiscardAndDrawCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "discard_and_draw",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initRevealDeckCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "reveal_deck",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initDealHouseCardsCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "deal_house_cards",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initRevealHouseCardsCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "reveal_house_cards",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initPlayBaccaratCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "play_baccarat",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
+  async function initPassCardsCompDef(
+    program: Program<CardShuffler>,
+    owner: Keypair,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean
+  ): Promise<string> {
+    return initCompDef(
+      program,
+      owner,
+      "pass_cards",
+      uploadRawCircuit,
+      offchainSource
+    );
+  }
+
   async function initCompDef(
     program: Program<CardShuffler>,
     owner: Keypair,
@@ -466,6 +1371,19 @@ describe("CardShuffler", () => {
       store_hole_cards: "initStoreHoleCardsCompDef",
       reveal_community_cards: "initRevealCommunityCompDef",
       change_hand: "initChangeHandCompDef",
+      seat_player: "initSeatPlayerCompDef",
+      reveal_hole_cards: "initRevealHoleCardsCompDef",
+      resolve_showdown: "initResolveShowdownCompDef",
+      deal_dealer_hand: "initDealDealerHandCompDef",
+      player_hit: "initPlayerHitCompDef",
+      dealer_play: "initDealerPlayCompDef",
+      reshuffle_remaining: "initReshuffleRemainingCompDef",
+      discard_and_draw: "initDiscardAndDrawCompDef",
+      reveal_deck: "initRevealDeckCompDef",
+      deal_house_cards: "initDealHouseCardsCompDef",
+      reveal_house_cards: "initRevealHouseCardsCompDef",
+      play_baccarat: "initPlayBaccaratCompDef",
+      pass_cards: "initPassCardsCompDef",
     };
 
     const methodName = methodNameMap[name];
@@ -0,0 +1,381 @@
+//! Cards as dealt by the card_shuffler circuits.
+//!
+//! A card's index is `suit * 13 + rank`, with ranks numbered two = 0 ... ace = 12
+//! and suits ordered clubs, diamonds, hearts, spades. So `0` is the two of clubs,
+//! `12` the ace of clubs, `13` the two of diamonds and `51` the ace of spades.
+//! Index 52 is a joker and [`EMPTY_CARD_MARKER`] marks an empty slot.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{DECK_LIMBS, EMPTY_CARD_MARKER, MAX_HOLE_CARDS};
+
+/// Card index used for a joker
+pub const JOKER: u8 = 52;
+
+/// Card rank, low to high
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
+    /// Rank number as used in card indices and `DeckSpec::rank_mask` (two = 0)
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_index(index: u8) -> Option<Rank> {
+        Rank::ALL.get(index as usize).copied()
+    }
+
+    /// Rank character in standard notation (`2`-`9`, `T`, `J`, `Q`, `K`, `A`)
+    pub fn symbol(self) -> char {
+        b"23456789TJQKA"[self as usize] as char
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<Rank> {
+        Rank::ALL
+            .into_iter()
+            .find(|rank| rank.symbol() == symbol.to_ascii_uppercase())
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Card suit, in index order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+    /// Suit number as used in card indices and `DeckSpec::suit_mask` (clubs = 0)
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_index(index: u8) -> Option<Suit> {
+        Suit::ALL.get(index as usize).copied()
+    }
+
+    /// Suit character in standard notation (`c`, `d`, `h`, `s`)
+    pub fn symbol(self) -> char {
+        b"cdhs"[self as usize] as char
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<Suit> {
+        Suit::ALL
+            .into_iter()
+            .find(|suit| suit.symbol() == symbol.to_ascii_lowercase())
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// A single card: `0..52` for the standard cards, [`JOKER`] for a joker.
+/// Displays and parses in standard notation (`As`, `Td`, `2c`), with `Jk` for a joker
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Card(u8);
+
+impl Card {
+    pub const JOKER: Card = Card(JOKER);
+
+    pub fn new(rank: Rank, suit: Suit) -> Card {
+        Card(suit.index() * 13 + rank.index())
+    }
+
+    /// Reads a card slot from a `CardGame` or a decrypted hand, where
+    /// [`EMPTY_CARD_MARKER`] means no card
+    pub fn from_slot(index: u8) -> Result<Option<Card>, InvalidCard> {
+        match index {
+            EMPTY_CARD_MARKER => Ok(None),
+            _ => Card::try_from(index).map(Some),
+        }
+    }
+
+    /// Index of the card as used on-chain and in the circuits
+    pub fn index(self) -> u8 {
+        self.0
+    }
+
+    pub fn is_joker(self) -> bool {
+        self.0 == JOKER
+    }
+
+    /// The card's rank, or `None` for a joker
+    pub fn rank(self) -> Option<Rank> {
+        if self.is_joker() {
+            None
+        } else {
+            Rank::from_index(self.0 % 13)
+        }
+    }
+
+    /// The card's suit, or `None` for a joker
+    pub fn suit(self) -> Option<Suit> {
+        Suit::from_index(self.0 / 13)
+    }
+}
+
+impl TryFrom<u8> for Card {
+    type Error = InvalidCard;
+
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        if index <= JOKER {
+            Ok(Card(index))
+        } else {
+            Err(InvalidCard(index))
+        }
+    }
+}
+
+impl From<Card> for u8 {
+    fn from(card: Card) -> u8 {
+        card.0
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.rank(), self.suit()) {
+            (Some(rank), Some(suit)) => write!(f, "{rank}{suit}"),
+            _ => write!(f, "Jk"),
+        }
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("jk") {
+            return Ok(Card::JOKER);
+        }
+
+        let mut chars = s.chars();
+        let (Some(rank), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(ParseCardError);
+        };
+        let rank = Rank::from_symbol(rank).ok_or(ParseCardError)?;
+        let suit = Suit::from_symbol(suit).ok_or(ParseCardError)?;
+        Ok(Card::new(rank, suit))
+    }
+}
+
+/// A byte that is not a card index
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidCard(pub u8);
+
+impl fmt::Display for InvalidCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a card index", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCard {}
+
+/// A string that is not a card in standard notation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseCardError;
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a card such as \"As\", \"Td\" or \"Jk\"")
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// Reads card slots, skipping empty and invalid slots
+pub(crate) fn cards_from_slots(slots: &[u8]) -> Vec<Card> {
+    slots
+        .iter()
+        .filter_map(|&index| Card::from_slot(index).ok().flatten())
+        .collect()
+}
+
+/// Unpacks a decrypted `Hand` (11 slots of 6 bits, slot 0 in the lowest bits)
+/// into the cards of its first `hand_size` slots, in the order they were dealt.
+/// Slots holding the [`EMPTY_CARD_MARKER`] are skipped
+pub fn unpack_hand(packed: u128, hand_size: u8) -> Vec<Card> {
+    let slots: Vec<u8> = (0..MAX_HOLE_CARDS.min(hand_size as usize))
+        .map(|slot| ((packed >> (6 * slot)) & 0x3f) as u8)
+        .collect();
+    cards_from_slots(&slots)
+}
+
+/// Unpacks a revealed deck (21 cards of 6 bits per limb, first card in the
+/// lowest bits of limb 0) into its first `deck_size` cards, in dealing order
+pub fn unpack_deck(deck: &[u128; DECK_LIMBS], deck_size: u16) -> Vec<Card> {
+    let slots: Vec<u8> = (0..(deck_size as usize).min(21 * DECK_LIMBS))
+        .map(|slot| ((deck[slot / 21] >> (6 * (slot % 21))) & 0x3f) as u8)
+        .collect();
+    cards_from_slots(&slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Packs cards the way the circuits' `Hand::from_array` does
+    fn pack_hand(slots: [u8; MAX_HOLE_CARDS]) -> u128 {
+        slots
+            .iter()
+            .enumerate()
+            .map(|(i, &card)| 64u128.pow(i as u32) * card as u128)
+            .sum()
+    }
+
+    /// Packs cards the way the circuits' `Deck::from_array` does, padding the
+    /// unused slots with 63
+    fn pack_deck(cards: &[u8]) -> [u128; DECK_LIMBS] {
+        let mut deck = [0u128; DECK_LIMBS];
+        for i in 0..21 * DECK_LIMBS {
+            let card = cards.get(i).copied().unwrap_or(63);
+            deck[i / 21] += 64u128.pow((i % 21) as u32) * card as u128;
+        }
+        deck
+    }
+
+    fn card(s: &str) -> Card {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn every_index_round_trips_through_notation() {
+        for index in 0..=JOKER {
+            let card = Card::try_from(index).unwrap();
+            assert_eq!(card.index(), index);
+            assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+        }
+        assert_eq!(Card::try_from(EMPTY_CARD_MARKER), Err(InvalidCard(53)));
+    }
+
+    #[test]
+    fn parses_either_case() {
+        assert_eq!(card("as"), card("AS"));
+        assert_eq!(card("jK"), Card::JOKER);
+        assert_eq!("A".parse::<Card>(), Err(ParseCardError));
+        assert_eq!("Asd".parse::<Card>(), Err(ParseCardError));
+        assert_eq!("1s".parse::<Card>(), Err(ParseCardError));
+        assert_eq!("Ax".parse::<Card>(), Err(ParseCardError));
+    }
+
+    #[test]
+    fn reads_slots() {
+        assert_eq!(Card::from_slot(EMPTY_CARD_MARKER), Ok(None));
+        assert_eq!(Card::from_slot(JOKER), Ok(Some(Card::JOKER)));
+        assert_eq!(Card::from_slot(51), Ok(Some(card("As"))));
+        assert_eq!(Card::from_slot(63), Err(InvalidCard(63)));
+    }
+
+    #[test]
+    fn joker_has_no_rank_or_suit() {
+        assert!(Card::JOKER.is_joker());
+        assert_eq!(Card::JOKER.rank(), None);
+        assert_eq!(Card::JOKER.suit(), None);
+        assert_eq!(Card::JOKER.to_string(), "Jk");
+    }
+
+    #[test]
+    fn indices_match_the_standard_deck() {
+        // The circuits lay out a standard deck as indices 0..52, suit by suit
+        let mut index = 0;
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                let card = Card::new(rank, suit);
+                assert_eq!(card.index(), index);
+                assert_eq!(card.rank(), Some(rank));
+                assert_eq!(card.suit(), Some(suit));
+                index += 1;
+            }
+        }
+
+        assert_eq!(card("2c").index(), 0);
+        assert_eq!(card("Ac").index(), 12);
+        assert_eq!(card("2d").index(), 13);
+        assert_eq!(card("As").index(), 51);
+    }
+
+    #[test]
+    fn unpacks_hands() {
+        let mut slots = [EMPTY_CARD_MARKER; MAX_HOLE_CARDS];
+        slots[..4].copy_from_slice(&[51, 0, JOKER, 24]);
+        let packed = pack_hand(slots);
+
+        assert_eq!(
+            unpack_hand(packed, 4),
+            vec![card("As"), card("2c"), Card::JOKER, card("Kd")]
+        );
+        assert_eq!(unpack_hand(packed, 2), vec![card("As"), card("2c")]);
+        assert_eq!(unpack_hand(packed, 0), vec![]);
+        // Empty slots are skipped and the size is capped at the slot count
+        assert_eq!(unpack_hand(packed, u8::MAX), unpack_hand(packed, 4));
+
+        let full: Vec<u8> = (40..51).collect();
+        let packed = pack_hand(full.clone().try_into().unwrap());
+        let unpacked: Vec<u8> = unpack_hand(packed, 11).into_iter().map(u8::from).collect();
+        assert_eq!(unpacked, full);
+    }
+
+    #[test]
+    fn unpacks_decks() {
+        // Two standard decks and a joker span several limbs
+        let cards: Vec<u8> = (0..52).chain(0..52).chain([JOKER]).collect();
+        let deck = pack_deck(&cards);
+
+        let unpacked: Vec<u8> = unpack_deck(&deck, cards.len() as u16)
+            .into_iter()
+            .map(u8::from)
+            .collect();
+        assert_eq!(unpacked, cards);
+        // The first card of the second limb
+        assert_eq!(unpack_deck(&deck, 22)[21], card("Td"));
+        // Padding past the deck size is not a card
+        assert_eq!(unpack_deck(&deck, u16::MAX).len(), cards.len());
+    }
+}
@@ -0,0 +1,217 @@
+//! Helpers for calling the card_shuffler program from another program.
+//!
+//! The calling program's PDA signs as both `payer` and `authority`, so the
+//! PDA must hold enough lamports for rent and computation fees and must be
+//! the game's authority (set at `initialize_card_game`).
+//!
+//! Remaining accounts on the `CpiContext` register the caller for a completion
+//! notification: pass the calling program first, followed by the accounts its
+//! `on_card_shuffler_callback` instruction needs. The notification is delivered
+//! by [`crate::instruction::deliver_notification`] once the callback has landed
+//! (see [`crate::CardShufflerNotification`]).
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::{DeckSpec, EntropySeed, CARD_SHUFFLER_PROGRAM_ID};
+
+pub use crate::instruction::discriminators;
+
+pub mod accounts {
+    use anchor_lang::prelude::*;
+
+    /// Accounts of every card_shuffler instruction that queues an Arcium computation,
+    /// in the order the program expects them. The addresses can be derived with
+    /// [`crate::pda`].
+    pub struct QueueComputation<'info> {
+        pub payer: AccountInfo<'info>,
+        pub authority: AccountInfo<'info>,
+        pub sign_pda_account: AccountInfo<'info>,
+        pub mxe_account: AccountInfo<'info>,
+        pub mempool_account: AccountInfo<'info>,
+        pub executing_pool: AccountInfo<'info>,
+        pub computation_account: AccountInfo<'info>,
+        pub comp_def_account: AccountInfo<'info>,
+        pub cluster_account: AccountInfo<'info>,
+        pub pool_account: AccountInfo<'info>,
+        pub clock_account: AccountInfo<'info>,
+        pub system_program: AccountInfo<'info>,
+        pub arcium_program: AccountInfo<'info>,
+        pub card_game: AccountInfo<'info>,
+    }
+
+    impl ToAccountMetas for QueueComputation<'_> {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer.key(), true),
+                AccountMeta::new_readonly(self.authority.key(), true),
+                AccountMeta::new(self.sign_pda_account.key(), false),
+                AccountMeta::new_readonly(self.mxe_account.key(), false),
+                AccountMeta::new(self.mempool_account.key(), false),
+                AccountMeta::new(self.executing_pool.key(), false),
+                AccountMeta::new(self.computation_account.key(), false),
+                AccountMeta::new_readonly(self.comp_def_account.key(), false),
+                AccountMeta::new(self.cluster_account.key(), false),
+                AccountMeta::new(self.pool_account.key(), false),
+                AccountMeta::new_readonly(self.clock_account.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+                AccountMeta::new_readonly(self.arcium_program.key(), false),
+                AccountMeta::new(self.card_game.key(), false),
+            ]
+        }
+    }
+
+    impl<'info> ToAccountInfos<'info> for QueueComputation<'info> {
+        fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+            vec![
+                self.payer.clone(),
+                self.authority.clone(),
+                self.sign_pda_account.clone(),
+                self.mxe_account.clone(),
+                self.mempool_account.clone(),
+                self.executing_pool.clone(),
+                self.computation_account.clone(),
+                self.comp_def_account.clone(),
+                self.cluster_account.clone(),
+                self.pool_account.clone(),
+                self.clock_account.clone(),
+                self.system_program.clone(),
+                self.arcium_program.clone(),
+                self.card_game.clone(),
+            ]
+        }
+    }
+
+    /// Accounts of the card_shuffler instructions that only update the game
+    pub struct UpdateGame<'info> {
+        pub authority: AccountInfo<'info>,
+        pub card_game: AccountInfo<'info>,
+    }
+
+    impl ToAccountMetas for UpdateGame<'_> {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.authority.key(), true),
+                AccountMeta::new(self.card_game.key(), false),
+            ]
+        }
+    }
+
+    impl<'info> ToAccountInfos<'info> for UpdateGame<'info> {
+        fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+            vec![self.authority.clone(), self.card_game.clone()]
+        }
+    }
+}
+
+/// Creates a card game, shuffles the deck and deals `num_hole_cards` to seat 0.
+/// `ctx.accounts.authority` becomes the game's authority. The board holds up to
+/// `max_community_cards` (at most [`crate::MAX_COMMUNITY_CARDS`]). `salt_nonce`
+/// encrypts the deck commitment salt and must differ from `mxe_nonce`. Up to
+/// [`crate::MAX_ENTROPY_SEEDS`] `entropy_seeds` are mixed into the shuffle
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_card_game<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::QueueComputation<'info>>,
+    computation_offset: u64,
+    game_id: u64,
+    mxe_nonce: u128,
+    salt_nonce: u128,
+    client_pubkey: [u8; 32],
+    client_nonce: u128,
+    num_hole_cards: u8,
+    deck_spec: DeckSpec,
+    max_community_cards: u8,
+    entropy_seeds: &[EntropySeed],
+) -> Result<()> {
+    invoke(
+        ctx,
+        discriminators::INITIALIZE_CARD_GAME,
+        (
+            computation_offset,
+            game_id,
+            mxe_nonce,
+            salt_nonce,
+            client_pubkey,
+            client_nonce,
+            num_hole_cards,
+            deck_spec,
+            max_community_cards,
+            entropy_seeds,
+        ),
+    )
+}
+
+/// Deals `num_new_cards` more hole cards to `seat`, after burning `num_burn`
+pub fn store_hole_cards<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::QueueComputation<'info>>,
+    computation_offset: u64,
+    game_id: u64,
+    seat: u8,
+    num_new_cards: u8,
+    num_burn: u8,
+) -> Result<()> {
+    invoke(
+        ctx,
+        discriminators::STORE_HOLE_CARDS,
+        (computation_offset, game_id, seat, num_new_cards, num_burn),
+    )
+}
+
+/// Reveals the next `num_cards_to_reveal` community cards, after burning `num_burn`
+pub fn reveal_community_cards<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::QueueComputation<'info>>,
+    computation_offset: u64,
+    game_id: u64,
+    num_cards_to_reveal: u8,
+    num_burn: u8,
+) -> Result<()> {
+    invoke(
+        ctx,
+        discriminators::REVEAL_COMMUNITY_CARDS,
+        (computation_offset, game_id, num_cards_to_reveal, num_burn),
+    )
+}
+
+/// Clears the community cards for the next hand. Fails while a computation is
+/// in flight, so call it before queueing the hand's first computation
+pub fn clear_board<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::UpdateGame<'info>>,
+    game_id: u64,
+) -> Result<()> {
+    invoke(ctx, discriminators::CLEAR_BOARD, game_id)
+}
+
+/// Replaces `seat`'s hand with a fresh empty one encrypted under `new_nonce`
+pub fn change_hand<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::QueueComputation<'info>>,
+    computation_offset: u64,
+    game_id: u64,
+    seat: u8,
+    new_nonce: u128,
+) -> Result<()> {
+    invoke(
+        ctx,
+        discriminators::CHANGE_HAND,
+        (computation_offset, game_id, seat, new_nonce),
+    )
+}
+
+/// Serializes the instruction arguments after the discriminator and invokes
+/// card_shuffler with the context's signer seeds
+fn invoke<'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    discriminator: [u8; 8],
+    args: impl AnchorSerialize,
+) -> Result<()> {
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data)?;
+
+    let ix = Instruction {
+        program_id: CARD_SHUFFLER_PROGRAM_ID,
+        accounts: ctx.to_account_metas(None),
+        data,
+    };
+
+    invoke_signed(&ix, &ctx.to_account_infos(), ctx.signer_seeds).map_err(Into::into)
+}
@@ -0,0 +1,416 @@
+//! Off-chain decryption of values card_shuffler encrypts to a player.
+//!
+//! Hands are encrypted with the Rescue cipher in counter mode, keyed by the x25519
+//! shared secret between the player's key and the MXE's key. This mirrors
+//! `x25519.getSharedSecret` and `RescueCipher` from Arcium's TypeScript client.
+//!
+//! ```ignore
+//! let secret = shared_secret(&player_secret_key, &mxe_public_key);
+//! let cipher = RescueCipher::new(&secret);
+//! let cards = card_game.seats[0].decrypt_hole_cards(&cipher)?;
+//! ```
+
+use std::fmt;
+
+use num_bigint::BigUint;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use crate::card::{unpack_hand, Card};
+use crate::{EntropySeed, Seat};
+
+/// Derives the x25519 public key to register for `secret_key`
+/// (the `client_pubkey` of `initialize_card_game` and `seat_player`)
+pub fn public_key(secret_key: &[u8; 32]) -> [u8; 32] {
+    x25519_dalek::x25519(*secret_key, x25519_dalek::X25519_BASEPOINT_BYTES)
+}
+
+/// Derives the secret shared between a player's x25519 key and the MXE's public key
+pub fn shared_secret(secret_key: &[u8; 32], mxe_public_key: &[u8; 32]) -> [u8; 32] {
+    x25519_dalek::x25519(*secret_key, *mxe_public_key)
+}
+
+/// Decrypting produced a value that is not a u128, which means the wrong key or
+/// nonce was used
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecryptError;
+
+impl fmt::Display for DecryptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "decrypted value is out of range (wrong key or nonce?)")
+    }
+}
+
+impl std::error::Error for DecryptError {}
+
+/// Rescue cipher keyed by an x25519 shared secret
+pub struct RescueCipher {
+    rescue: Rescue,
+}
+
+impl RescueCipher {
+    pub fn new(shared_secret: &[u8; 32]) -> Self {
+        // Key derivation per NIST SP 800-56C, section 4, option 1:
+        // H(counter || Z || L), with counter = 1 and L the key length in field elements
+        let hasher = Rescue::hash();
+        let key = hasher.digest(vec![
+            BigUint::from(1u8),
+            from_le_bytes(shared_secret),
+            BigUint::from(CIPHER_STATE_SIZE),
+        ]);
+
+        RescueCipher {
+            rescue: Rescue::cipher(key),
+        }
+    }
+
+    /// Encrypts each value into a 32-byte ciphertext under `nonce`
+    pub fn encrypt(&self, plaintexts: &[u128], nonce: u128) -> Vec<[u8; 32]> {
+        self.keystream(plaintexts.len(), nonce)
+            .zip(plaintexts)
+            .map(|(key, &plaintext)| to_le_bytes(&((key + plaintext) % modulus())))
+            .collect()
+    }
+
+    /// Decrypts ciphertexts produced under `nonce`
+    pub fn decrypt(
+        &self,
+        ciphertexts: &[[u8; 32]],
+        nonce: u128,
+    ) -> Result<Vec<u128>, DecryptError> {
+        let p = modulus();
+        self.keystream(ciphertexts.len(), nonce)
+            .zip(ciphertexts)
+            .map(|(key, ciphertext)| {
+                let plaintext = (from_le_bytes(ciphertext) + &p - key) % &p;
+                u128::try_from(plaintext).map_err(|_| DecryptError)
+            })
+            .collect()
+    }
+
+    /// Decrypts a packed `Hand` and returns its first `hand_size` cards
+    pub fn decrypt_hand(
+        &self,
+        hand: &[u8; 32],
+        nonce: u128,
+        hand_size: u8,
+    ) -> Result<Vec<Card>, DecryptError> {
+        let packed = self.decrypt(&[*hand], nonce)?[0];
+        Ok(unpack_hand(packed, hand_size))
+    }
+
+    /// Counter mode: block i encrypts the counter `[nonce, i, 0, 0, 0]`
+    fn keystream(&self, len: usize, nonce: u128) -> impl Iterator<Item = BigUint> + '_ {
+        (0..len.div_ceil(CIPHER_STATE_SIZE))
+            .flat_map(move |block| {
+                let mut counter = vec![BigUint::default(); CIPHER_STATE_SIZE];
+                counter[0] = BigUint::from(nonce);
+                counter[1] = BigUint::from(block);
+                self.rescue.permute(counter)
+            })
+            .take(len)
+    }
+}
+
+impl Seat {
+    /// Decrypts this seat's hole cards with the seat owner's cipher
+    pub fn decrypt_hole_cards(&self, cipher: &RescueCipher) -> Result<Vec<Card>, DecryptError> {
+        cipher.decrypt_hand(
+            &self.hole_cards,
+            self.hole_cards_nonce,
+            self.hole_cards_size,
+        )
+    }
+}
+
+impl EntropySeed {
+    /// Encrypts a shuffle seed with the player's cipher. `enc_pubkey` is the
+    /// player's x25519 public key, and `nonce` must not be reused with that key
+    pub fn encrypt(
+        cipher: &RescueCipher,
+        enc_pubkey: [u8; 32],
+        seed: u128,
+        nonce: u128,
+    ) -> EntropySeed {
+        EntropySeed {
+            enc_pubkey,
+            nonce,
+            ciphertext: cipher.encrypt(&[seed], nonce)[0],
+        }
+    }
+}
+
+/// Rescue S-box exponent; the smallest prime coprime to p - 1
+const ALPHA: u32 = 5;
+const CIPHER_STATE_SIZE: usize = 5;
+/// Rounds for 128-bit security at state size 5
+const CIPHER_ROUNDS: usize = 10;
+const HASH_STATE_SIZE: usize = 12;
+const HASH_CAPACITY: usize = 5;
+const HASH_RATE: usize = HASH_STATE_SIZE - HASH_CAPACITY;
+const HASH_DIGEST_LENGTH: usize = 5;
+const HASH_SECURITY_LEVEL: usize = 256;
+/// Rounds for 256-bit security at state size 12 (Rescue-Prime round formula)
+const HASH_ROUNDS: usize = 8;
+/// Bytes of SHAKE256 output per sampled field element; 16 more than a field
+/// element so the reduction mod p is close to uniform
+const SAMPLE_BYTES: usize = 48;
+
+/// Modulus of the Curve25519 base field, 2^255 - 19
+fn modulus() -> BigUint {
+    (BigUint::from(1u8) << 255u32) - 19u32
+}
+
+fn from_le_bytes(bytes: &[u8]) -> BigUint {
+    BigUint::from_bytes_le(bytes) % modulus()
+}
+
+fn to_le_bytes(value: &BigUint) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    let le = value.to_bytes_le();
+    bytes[..le.len()].copy_from_slice(&le);
+    bytes
+}
+
+/// A Rescue permutation over the Curve25519 base field, either keyed (cipher)
+/// or with fixed round constants (Rescue-Prime hash)
+struct Rescue {
+    is_cipher: bool,
+    alpha_inverse: BigUint,
+    mds: Vec<Vec<BigUint>>,
+    /// 2 * rounds + 1 vectors added between the half rounds
+    round_keys: Vec<Vec<BigUint>>,
+}
+
+impl Rescue {
+    fn hash() -> Self {
+        let mut xof = Shake256::default();
+        xof.update(
+            format!(
+                "Rescue-XLIX({},{},{},{})",
+                modulus(),
+                HASH_STATE_SIZE,
+                HASH_CAPACITY,
+                HASH_SECURITY_LEVEL
+            )
+            .as_bytes(),
+        );
+        let mut reader = xof.finalize_xof();
+
+        let mut round_keys = vec![vec![BigUint::default(); HASH_STATE_SIZE]];
+        round_keys
+            .extend((0..2 * HASH_ROUNDS).map(|_| sample_vector(&mut reader, HASH_STATE_SIZE)));
+
+        Rescue {
+            is_cipher: false,
+            alpha_inverse: alpha_inverse(),
+            mds: cauchy_matrix(HASH_STATE_SIZE),
+            round_keys,
+        }
+    }
+
+    fn cipher(key: Vec<BigUint>) -> Self {
+        let p = modulus();
+        let mut xof = Shake256::default();
+        xof.update(b"encrypt everything, compute everything");
+        let mut reader = xof.finalize_xof();
+
+        // Key schedule constants: c_0, then c_{r+1} = M c_r + v
+        let matrix: Vec<Vec<BigUint>> = (0..CIPHER_STATE_SIZE)
+            .map(|_| sample_vector(&mut reader, CIPHER_STATE_SIZE))
+            .collect();
+        let affine = sample_vector(&mut reader, CIPHER_STATE_SIZE);
+        let mut constants = vec![sample_vector(&mut reader, CIPHER_STATE_SIZE)];
+        for r in 0..2 * CIPHER_ROUNDS {
+            let next = mat_mul(&matrix, &constants[r])
+                .into_iter()
+                .zip(&affine)
+                .map(|(x, v)| (x + v) % &p)
+                .collect();
+            constants.push(next);
+        }
+
+        let mut rescue = Rescue {
+            is_cipher: true,
+            alpha_inverse: alpha_inverse(),
+            mds: cauchy_matrix(CIPHER_STATE_SIZE),
+            round_keys: constants,
+        };
+        // The round keys are the intermediate states of permuting the key
+        rescue.round_keys = rescue.states(key);
+        rescue
+    }
+
+    fn permute(&self, state: Vec<BigUint>) -> Vec<BigUint> {
+        self.states(state).pop().unwrap()
+    }
+
+    fn states(&self, state: Vec<BigUint>) -> Vec<Vec<BigUint>> {
+        let p = modulus();
+        let alpha = BigUint::from(ALPHA);
+        // The cipher starts each round with the inverse S-box, the hash with the S-box
+        let (even, odd) = if self.is_cipher {
+            (&self.alpha_inverse, &alpha)
+        } else {
+            (&alpha, &self.alpha_inverse)
+        };
+
+        let mut states = vec![add(&state, &self.round_keys[0], &p)];
+        for r in 0..self.round_keys.len() - 1 {
+            let exponent = if r % 2 == 0 { even } else { odd };
+            let sboxed: Vec<BigUint> = states[r].iter().map(|x| x.modpow(exponent, &p)).collect();
+            let mixed = mat_mul(&self.mds, &sboxed);
+            states.push(add(&mixed, &self.round_keys[r + 1], &p));
+        }
+        states
+    }
+
+    /// Rescue-Prime sponge: pad with 1 then zeros to a multiple of the rate,
+    /// absorb rate-sized blocks, and squeeze the digest
+    fn digest(&self, mut message: Vec<BigUint>) -> Vec<BigUint> {
+        let p = modulus();
+        message.push(BigUint::from(1u8));
+        message.resize(
+            message.len().next_multiple_of(HASH_RATE),
+            BigUint::default(),
+        );
+
+        let mut state = vec![BigUint::default(); HASH_STATE_SIZE];
+        for block in message.chunks(HASH_RATE) {
+            for (s, m) in state.iter_mut().zip(block) {
+                *s = (&*s + m) % &p;
+            }
+            state = self.permute(state);
+        }
+
+        state.truncate(HASH_DIGEST_LENGTH);
+        state
+    }
+}
+
+fn alpha_inverse() -> BigUint {
+    BigUint::from(ALPHA)
+        .modinv(&(modulus() - 1u32))
+        .expect("alpha is coprime to p - 1")
+}
+
+/// Cauchy matrix with entries 1 / (i + j) for i, j in 1..=size, which is MDS
+fn cauchy_matrix(size: usize) -> Vec<Vec<BigUint>> {
+    let p = modulus();
+    (1..=size)
+        .map(|i| {
+            (1..=size)
+                .map(|j| BigUint::from(i + j).modinv(&p).expect("p is prime"))
+                .collect()
+        })
+        .collect()
+}
+
+fn sample_vector(reader: &mut impl XofReader, len: usize) -> Vec<BigUint> {
+    (0..len)
+        .map(|_| {
+            let mut bytes = [0u8; SAMPLE_BYTES];
+            reader.read(&mut bytes);
+            from_le_bytes(&bytes)
+        })
+        .collect()
+}
+
+fn mat_mul(matrix: &[Vec<BigUint>], vector: &[BigUint]) -> Vec<BigUint> {
+    let p = modulus();
+    matrix
+        .iter()
+        .map(|row| row.iter().zip(vector).map(|(a, b)| a * b).sum::<BigUint>() % &p)
+        .collect()
+}
+
+fn add(a: &[BigUint], b: &[BigUint], p: &BigUint) -> Vec<BigUint> {
+    a.iter().zip(b).map(|(x, y)| (x + y) % p).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{Rank, Suit};
+
+    // RFC 7748, section 6.1
+    const ALICE_SECRET_KEY: &str =
+        "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+    const ALICE_PUBLIC_KEY: &str =
+        "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+    const BOB_PUBLIC_KEY: &str = "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f";
+    const SHARED_SECRET: &str = "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742";
+
+    const NONCE: u128 = 0x0102030405060708090a0b0c0d0e0f10;
+
+    /// As, Kd and nine empty slots, packed like the circuits' `Hand`
+    const PACKED_HAND: u128 = 0x35d75d75d75d75633;
+
+    /// `plaintexts()` encrypted under `NONCE` with the cipher keyed by
+    /// `SHARED_SECRET`. `arcium_jobs/card_shuffler/scripts/crypto_vectors.mjs`
+    /// prints the same vectors with Arcium's TypeScript client
+    const CIPHERTEXTS: [&str; 6] = [
+        "b38e18f79feb7887a4b118a30d82d73d018d5d6b14a9331c21f4672004b83569",
+        "c3677cce509f8ce5b82e7011cac5897da5622bdefe9fdd90a2916ce14a7df92a",
+        "f4b841d56de30742d7b6f7731886c20935698b94b99cae9c65e2d4a83f1da25f",
+        "c91eb4541edde0f3b47d59f5b311e604d899804d22b09303de93827a91c16f63",
+        "89d6bdcf50d38d1cd9caf9f62f5e85a96e680e8aabf68cea53647f63cb569039",
+        "3fe7f9201d3c4d130e10602c29dfc2d4b6efe4898e41cb616e9d40de5b723d27",
+    ];
+
+    fn bytes(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+        }
+        bytes
+    }
+
+    /// Six values, so the keystream spans two counter blocks
+    fn plaintexts() -> Vec<u128> {
+        vec![PACKED_HAND, 0, 1, u128::MAX, 42, 7]
+    }
+
+    fn cipher() -> RescueCipher {
+        RescueCipher::new(&bytes(SHARED_SECRET))
+    }
+
+    #[test]
+    fn x25519_known_answers() {
+        let secret_key = bytes(ALICE_SECRET_KEY);
+        assert_eq!(public_key(&secret_key), bytes(ALICE_PUBLIC_KEY));
+        assert_eq!(
+            shared_secret(&secret_key, &bytes(BOB_PUBLIC_KEY)),
+            bytes(SHARED_SECRET)
+        );
+    }
+
+    #[test]
+    fn rescue_cipher_known_answers() {
+        let expected: Vec<[u8; 32]> = CIPHERTEXTS.iter().map(|hex| bytes(hex)).collect();
+        let cipher = cipher();
+        assert_eq!(cipher.encrypt(&plaintexts(), NONCE), expected);
+        assert_eq!(cipher.decrypt(&expected, NONCE), Ok(plaintexts()));
+    }
+
+    #[test]
+    fn entropy_seed_decrypts_as_hand() {
+        let cipher = cipher();
+        let enc_pubkey = public_key(&bytes(ALICE_SECRET_KEY));
+        let seed = EntropySeed::encrypt(&cipher, enc_pubkey, PACKED_HAND, NONCE);
+
+        assert_eq!(seed.enc_pubkey, enc_pubkey);
+        assert_eq!(seed.nonce, NONCE);
+        assert_eq!(
+            cipher.decrypt_hand(&seed.ciphertext, seed.nonce, 11),
+            Ok(vec![
+                Card::new(Rank::Ace, Suit::Spades),
+                Card::new(Rank::King, Suit::Diamonds),
+            ])
+        );
+        assert_eq!(
+            cipher.decrypt_hand(&seed.ciphertext, NONCE + 1, 11),
+            Err(DecryptError)
+        );
+    }
+}
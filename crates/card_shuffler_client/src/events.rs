@@ -0,0 +1,715 @@
+//! Events emitted by the card_shuffler program, and a parser that reads them
+//! from transaction logs.
+//!
+//! Anchor logs each event as `Program data: <base64>`, where the data is the
+//! event's discriminator followed by its Borsh encoding.
+//!
+//! ```ignore
+//! let logs = transaction.meta.log_messages;
+//! for event in events::parse_logs(&logs) {
+//!     if let CardShufflerEvent::CommunityCardsRevealed(event) = event { /* ... */ }
+//! }
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use solana_program::hash::hashv;
+
+use crate::card::{unpack_deck, Card};
+use crate::{ComputationKind, CARD_SHUFFLER_PROGRAM_ID, DECK_LIMBS, MAX_HOLE_CARDS, MAX_SEATS};
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DeckShuffledEvent {
+    pub game_id: u64,
+    pub deck_size: u16,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub num_hole_cards: u8,
+    pub deck_commitment: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct PlayerSeatedEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub player_pubkey: Pubkey,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub num_hole_cards: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct HoleCardsStoredEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub total_hole_cards: u8,
+    pub num_burned: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct CommunityCardsRevealedEvent {
+    pub game_id: u64,
+    /// Cards revealed by this computation (empty card marker past `num_revealed`)
+    pub new_cards: [u8; 5],
+    pub num_revealed: u8,
+    pub num_burned: u8,
+    /// Full board after the reveal
+    pub community_cards: [u8; 5],
+    pub community_cards_size: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct BoardClearedEvent {
+    pub game_id: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct HoleCardsRevealedEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub revealed_cards: [u8; MAX_HOLE_CARDS],
+    pub num_revealed: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct ShowdownResolvedEvent {
+    pub game_id: u64,
+    pub winners: u16,
+    pub hand_category: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DealerHandDealtEvent {
+    pub game_id: u64,
+    pub upcard: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct PlayerHitEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub total_hole_cards: u8,
+    pub busted: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct PlayerStoodEvent {
+    pub game_id: u64,
+    pub seat: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DealerPlayedEvent {
+    pub game_id: u64,
+    pub dealer_cards: [u8; MAX_HOLE_CARDS],
+    pub dealer_total: u8,
+    pub outcomes: [u8; MAX_SEATS],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DeckReshuffledEvent {
+    pub game_id: u64,
+    pub cards_remaining: u16,
+    pub deck_commitment: [u8; 32],
+    /// Commitment `deck_commitment` is chained to
+    pub previous_deck_commitment: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct CardsDiscardedEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub num_discarded: u8,
+    pub discard_pile_size: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DeckRevealedEvent {
+    pub game_id: u64,
+    /// Packed deck limbs (21 cards per limb, six bits each, first card lowest)
+    pub deck: [u128; DECK_LIMBS],
+    pub deck_salt: u128,
+    pub deck_commitment: [u8; 32],
+    /// Commitment `deck_commitment` is chained to, if the deck was reshuffled
+    pub previous_deck_commitment: Option<[u8; 32]>,
+    pub deck_size: u16,
+    pub cards_dealt: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct HouseKeySetEvent {
+    pub game_id: u64,
+    pub house_enc_pubkey: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct HouseCardsDealtEvent {
+    pub game_id: u64,
+    pub hole_cards: [u8; 32],
+    pub hole_cards_nonce: u128,
+    pub total_hole_cards: u8,
+    pub num_burned: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct HouseCardsRevealedEvent {
+    pub game_id: u64,
+    pub revealed_cards: [u8; MAX_HOLE_CARDS],
+    pub num_revealed: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct BaccaratPlayedEvent {
+    pub game_id: u64,
+    pub coup: u32,
+    pub player_cards: [u8; 3],
+    pub player_cards_size: u8,
+    pub banker_cards: [u8; 3],
+    pub banker_cards_size: u8,
+    pub player_total: u8,
+    pub banker_total: u8,
+    pub outcome: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct CardsPassedEvent {
+    pub game_id: u64,
+    pub sender_seat: u8,
+    pub recipient_seat: u8,
+    pub sender_hole_cards: [u8; 32],
+    pub sender_hole_cards_nonce: u128,
+    pub recipient_hole_cards: [u8; 32],
+    pub recipient_hole_cards_nonce: u128,
+    pub num_passed: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct ComputationAbortedEvent {
+    pub game_id: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct ComputationCancelledEvent {
+    pub game_id: u64,
+    pub offset: u64,
+    pub kind: ComputationKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct AuthorityTransferredEvent {
+    pub game_id: u64,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct CardGameClosedEvent {
+    pub game_id: u64,
+    pub destination: Pubkey,
+    pub lamports: u64,
+    pub num_seats: u8,
+    pub cards_dealt: u16,
+    pub deck_size: u16,
+    pub community_cards_size: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct HandChangedEvent {
+    pub game_id: u64,
+    pub seat: u8,
+    pub new_hand: [u8; 32],
+    pub new_nonce: u128,
+}
+
+impl Discriminator for DeckShuffledEvent {
+    const DISCRIMINATOR: &'static [u8] = &[183, 51, 10, 94, 4, 29, 4, 188];
+}
+
+impl Discriminator for PlayerSeatedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[116, 225, 29, 114, 54, 96, 111, 74];
+}
+
+impl Discriminator for HoleCardsStoredEvent {
+    const DISCRIMINATOR: &'static [u8] = &[149, 89, 20, 201, 35, 156, 15, 194];
+}
+
+impl Discriminator for CommunityCardsRevealedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[29, 29, 172, 193, 87, 83, 178, 50];
+}
+
+impl Discriminator for BoardClearedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[58, 74, 253, 159, 146, 9, 14, 159];
+}
+
+impl Discriminator for HoleCardsRevealedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[13, 137, 187, 90, 248, 138, 56, 7];
+}
+
+impl Discriminator for ShowdownResolvedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[135, 67, 231, 18, 89, 17, 68, 20];
+}
+
+impl Discriminator for DealerHandDealtEvent {
+    const DISCRIMINATOR: &'static [u8] = &[85, 139, 215, 61, 12, 127, 5, 151];
+}
+
+impl Discriminator for PlayerHitEvent {
+    const DISCRIMINATOR: &'static [u8] = &[137, 179, 235, 199, 251, 72, 61, 62];
+}
+
+impl Discriminator for PlayerStoodEvent {
+    const DISCRIMINATOR: &'static [u8] = &[134, 217, 143, 59, 149, 135, 44, 67];
+}
+
+impl Discriminator for DealerPlayedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[61, 217, 171, 248, 94, 179, 14, 93];
+}
+
+impl Discriminator for DeckReshuffledEvent {
+    const DISCRIMINATOR: &'static [u8] = &[215, 195, 117, 31, 43, 129, 232, 216];
+}
+
+impl Discriminator for CardsDiscardedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[101, 185, 107, 255, 189, 40, 151, 241];
+}
+
+impl Discriminator for DeckRevealedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[72, 153, 108, 169, 102, 26, 56, 166];
+}
+
+impl Discriminator for HouseKeySetEvent {
+    const DISCRIMINATOR: &'static [u8] = &[111, 49, 51, 233, 51, 28, 9, 86];
+}
+
+impl Discriminator for HouseCardsDealtEvent {
+    const DISCRIMINATOR: &'static [u8] = &[217, 119, 199, 108, 139, 155, 135, 119];
+}
+
+impl Discriminator for HouseCardsRevealedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[158, 197, 93, 187, 223, 176, 152, 113];
+}
+
+impl Discriminator for BaccaratPlayedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[253, 177, 252, 61, 27, 183, 14, 72];
+}
+
+impl Discriminator for CardsPassedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[45, 127, 133, 55, 222, 83, 27, 197];
+}
+
+impl Discriminator for ComputationAbortedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[231, 232, 186, 217, 82, 162, 86, 202];
+}
+
+impl Discriminator for ComputationCancelledEvent {
+    const DISCRIMINATOR: &'static [u8] = &[221, 161, 40, 205, 50, 177, 44, 127];
+}
+
+impl Discriminator for AuthorityTransferredEvent {
+    const DISCRIMINATOR: &'static [u8] = &[54, 31, 118, 237, 152, 5, 201, 11];
+}
+
+impl Discriminator for CardGameClosedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[115, 55, 218, 78, 144, 97, 222, 169];
+}
+
+impl Discriminator for HandChangedEvent {
+    const DISCRIMINATOR: &'static [u8] = &[29, 99, 101, 226, 114, 26, 255, 89];
+}
+
+impl DeckRevealedEvent {
+    /// SHA-256 of the salt followed by the deck limbs, each as 16 little-endian
+    /// bytes; this is what the program committed to when the deck was shuffled.
+    /// After a reshuffle the hash starts with `previous_deck_commitment`
+    pub fn computed_commitment(&self) -> [u8; 32] {
+        let salt = self.deck_salt.to_le_bytes();
+        let limbs: Vec<[u8; 16]> = self.deck.iter().map(|limb| limb.to_le_bytes()).collect();
+        let mut data: Vec<&[u8]> = Vec::new();
+        if let Some(previous) = &self.previous_deck_commitment {
+            data.push(previous);
+        }
+        data.push(&salt);
+        data.extend(limbs.iter().map(|limb| limb.as_slice()));
+        hashv(&data).to_bytes()
+    }
+
+    /// Whether the revealed deck and salt match the commitment published at shuffle time
+    pub fn verify(&self) -> bool {
+        self.computed_commitment() == self.deck_commitment
+    }
+
+    /// Like [`Self::verify`], and also checks that the commitment chains back
+    /// through `reshuffles` (the game's `DeckReshuffledEvent`s, in order) to
+    /// `shuffle_commitment`, the one in its `DeckShuffledEvent`
+    pub fn verify_chain(
+        &self,
+        shuffle_commitment: [u8; 32],
+        reshuffles: &[DeckReshuffledEvent],
+    ) -> bool {
+        let mut expected = shuffle_commitment;
+        for reshuffle in reshuffles {
+            if reshuffle.previous_deck_commitment != expected {
+                return false;
+            }
+            expected = reshuffle.deck_commitment;
+        }
+        let previous = reshuffles
+            .last()
+            .map(|reshuffle| reshuffle.previous_deck_commitment);
+        self.previous_deck_commitment == previous
+            && self.deck_commitment == expected
+            && self.verify()
+    }
+
+    /// The cards of the deck in dealing order; the first `cards_dealt` were dealt
+    pub fn cards(&self) -> Vec<Card> {
+        unpack_deck(&self.deck, self.deck_size)
+    }
+}
+
+/// Any event emitted by the card_shuffler program
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CardShufflerEvent {
+    DeckShuffled(DeckShuffledEvent),
+    PlayerSeated(PlayerSeatedEvent),
+    HoleCardsStored(HoleCardsStoredEvent),
+    CommunityCardsRevealed(CommunityCardsRevealedEvent),
+    BoardCleared(BoardClearedEvent),
+    HoleCardsRevealed(HoleCardsRevealedEvent),
+    ShowdownResolved(ShowdownResolvedEvent),
+    DealerHandDealt(DealerHandDealtEvent),
+    PlayerHit(PlayerHitEvent),
+    PlayerStood(PlayerStoodEvent),
+    DealerPlayed(DealerPlayedEvent),
+    DeckReshuffled(DeckReshuffledEvent),
+    CardsDiscarded(CardsDiscardedEvent),
+    DeckRevealed(Box<DeckRevealedEvent>),
+    HouseKeySet(HouseKeySetEvent),
+    HouseCardsDealt(HouseCardsDealtEvent),
+    HouseCardsRevealed(HouseCardsRevealedEvent),
+    BaccaratPlayed(BaccaratPlayedEvent),
+    CardsPassed(CardsPassedEvent),
+    ComputationAborted(ComputationAbortedEvent),
+    ComputationCancelled(ComputationCancelledEvent),
+    AuthorityTransferred(AuthorityTransferredEvent),
+    CardGameClosed(CardGameClosedEvent),
+    HandChanged(HandChangedEvent),
+}
+
+impl CardShufflerEvent {
+    /// Decodes an event from its discriminator and Borsh encoding. Returns `None`
+    /// for an unknown discriminator or data that does not match the event
+    pub fn decode(data: &[u8]) -> Option<CardShufflerEvent> {
+        let (discriminator, data) = data.split_at_checked(8)?;
+        let event = match discriminator {
+            DeckShuffledEvent::DISCRIMINATOR => Self::DeckShuffled(decode(data)?),
+            PlayerSeatedEvent::DISCRIMINATOR => Self::PlayerSeated(decode(data)?),
+            HoleCardsStoredEvent::DISCRIMINATOR => Self::HoleCardsStored(decode(data)?),
+            CommunityCardsRevealedEvent::DISCRIMINATOR => {
+                Self::CommunityCardsRevealed(decode(data)?)
+            }
+            BoardClearedEvent::DISCRIMINATOR => Self::BoardCleared(decode(data)?),
+            HoleCardsRevealedEvent::DISCRIMINATOR => Self::HoleCardsRevealed(decode(data)?),
+            ShowdownResolvedEvent::DISCRIMINATOR => Self::ShowdownResolved(decode(data)?),
+            DealerHandDealtEvent::DISCRIMINATOR => Self::DealerHandDealt(decode(data)?),
+            PlayerHitEvent::DISCRIMINATOR => Self::PlayerHit(decode(data)?),
+            PlayerStoodEvent::DISCRIMINATOR => Self::PlayerStood(decode(data)?),
+            DealerPlayedEvent::DISCRIMINATOR => Self::DealerPlayed(decode(data)?),
+            DeckReshuffledEvent::DISCRIMINATOR => Self::DeckReshuffled(decode(data)?),
+            CardsDiscardedEvent::DISCRIMINATOR => Self::CardsDiscarded(decode(data)?),
+            DeckRevealedEvent::DISCRIMINATOR => Self::DeckRevealed(Box::new(decode(data)?)),
+            HouseKeySetEvent::DISCRIMINATOR => Self::HouseKeySet(decode(data)?),
+            HouseCardsDealtEvent::DISCRIMINATOR => Self::HouseCardsDealt(decode(data)?),
+            HouseCardsRevealedEvent::DISCRIMINATOR => Self::HouseCardsRevealed(decode(data)?),
+            BaccaratPlayedEvent::DISCRIMINATOR => Self::BaccaratPlayed(decode(data)?),
+            CardsPassedEvent::DISCRIMINATOR => Self::CardsPassed(decode(data)?),
+            ComputationAbortedEvent::DISCRIMINATOR => Self::ComputationAborted(decode(data)?),
+            ComputationCancelledEvent::DISCRIMINATOR => Self::ComputationCancelled(decode(data)?),
+            AuthorityTransferredEvent::DISCRIMINATOR => Self::AuthorityTransferred(decode(data)?),
+            CardGameClosedEvent::DISCRIMINATOR => Self::CardGameClosed(decode(data)?),
+            HandChangedEvent::DISCRIMINATOR => Self::HandChanged(decode(data)?),
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    /// Game the event belongs to
+    pub fn game_id(&self) -> u64 {
+        match self {
+            Self::DeckShuffled(event) => event.game_id,
+            Self::PlayerSeated(event) => event.game_id,
+            Self::HoleCardsStored(event) => event.game_id,
+            Self::CommunityCardsRevealed(event) => event.game_id,
+            Self::BoardCleared(event) => event.game_id,
+            Self::HoleCardsRevealed(event) => event.game_id,
+            Self::ShowdownResolved(event) => event.game_id,
+            Self::DealerHandDealt(event) => event.game_id,
+            Self::PlayerHit(event) => event.game_id,
+            Self::PlayerStood(event) => event.game_id,
+            Self::DealerPlayed(event) => event.game_id,
+            Self::DeckReshuffled(event) => event.game_id,
+            Self::CardsDiscarded(event) => event.game_id,
+            Self::DeckRevealed(event) => event.game_id,
+            Self::HouseKeySet(event) => event.game_id,
+            Self::HouseCardsDealt(event) => event.game_id,
+            Self::HouseCardsRevealed(event) => event.game_id,
+            Self::BaccaratPlayed(event) => event.game_id,
+            Self::CardsPassed(event) => event.game_id,
+            Self::ComputationAborted(event) => event.game_id,
+            Self::ComputationCancelled(event) => event.game_id,
+            Self::AuthorityTransferred(event) => event.game_id,
+            Self::CardGameClosed(event) => event.game_id,
+            Self::HandChanged(event) => event.game_id,
+        }
+    }
+}
+
+/// Extracts the card_shuffler events from a transaction's log messages, in the
+/// order they were emitted. `Program data:` lines logged by other programs
+/// (including programs card_shuffler calls, or that call it) are skipped, as
+/// are lines that do not decode to a card_shuffler event
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<CardShufflerEvent> {
+    let card_shuffler = CARD_SHUFFLER_PROGRAM_ID.to_string();
+    // Programs currently executing, innermost last
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if invocations.last() != Some(&card_shuffler.as_str()) {
+                continue;
+            }
+            if let Some(event) = BASE64_STANDARD
+                .decode(data)
+                .ok()
+                .and_then(|data| CardShufflerEvent::decode(&data))
+            {
+                events.push(event);
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(program), Some("invoke")) => invocations.push(program),
+                (Some(_), Some("success" | "failed:")) => {
+                    invocations.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
+
+fn decode<T: AnchorDeserialize>(data: &[u8]) -> Option<T> {
+    T::try_from_slice(data).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::hash::hash;
+
+    use super::*;
+
+    const PROGRAM: &str = "DQxanaqqWcTYvVhrKbeoY6q52NrGksWBL6vSbuVipnS7";
+
+    fn encode<T: Discriminator + AnchorSerialize>(event: &T) -> String {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        event.serialize(&mut data).unwrap();
+        BASE64_STANDARD.encode(data)
+    }
+
+    #[test]
+    fn discriminators_match_event_names() {
+        let events: [(&str, &[u8]); 24] = [
+            ("DeckShuffledEvent", DeckShuffledEvent::DISCRIMINATOR),
+            ("PlayerSeatedEvent", PlayerSeatedEvent::DISCRIMINATOR),
+            ("HoleCardsStoredEvent", HoleCardsStoredEvent::DISCRIMINATOR),
+            (
+                "CommunityCardsRevealedEvent",
+                CommunityCardsRevealedEvent::DISCRIMINATOR,
+            ),
+            ("BoardClearedEvent", BoardClearedEvent::DISCRIMINATOR),
+            (
+                "HoleCardsRevealedEvent",
+                HoleCardsRevealedEvent::DISCRIMINATOR,
+            ),
+            (
+                "ShowdownResolvedEvent",
+                ShowdownResolvedEvent::DISCRIMINATOR,
+            ),
+            ("DealerHandDealtEvent", DealerHandDealtEvent::DISCRIMINATOR),
+            ("PlayerHitEvent", PlayerHitEvent::DISCRIMINATOR),
+            ("PlayerStoodEvent", PlayerStoodEvent::DISCRIMINATOR),
+            ("DealerPlayedEvent", DealerPlayedEvent::DISCRIMINATOR),
+            ("DeckReshuffledEvent", DeckReshuffledEvent::DISCRIMINATOR),
+            ("CardsDiscardedEvent", CardsDiscardedEvent::DISCRIMINATOR),
+            ("DeckRevealedEvent", DeckRevealedEvent::DISCRIMINATOR),
+            ("HouseKeySetEvent", HouseKeySetEvent::DISCRIMINATOR),
+            ("HouseCardsDealtEvent", HouseCardsDealtEvent::DISCRIMINATOR),
+            (
+                "HouseCardsRevealedEvent",
+                HouseCardsRevealedEvent::DISCRIMINATOR,
+            ),
+            ("BaccaratPlayedEvent", BaccaratPlayedEvent::DISCRIMINATOR),
+            ("CardsPassedEvent", CardsPassedEvent::DISCRIMINATOR),
+            (
+                "ComputationAbortedEvent",
+                ComputationAbortedEvent::DISCRIMINATOR,
+            ),
+            (
+                "ComputationCancelledEvent",
+                ComputationCancelledEvent::DISCRIMINATOR,
+            ),
+            (
+                "AuthorityTransferredEvent",
+                AuthorityTransferredEvent::DISCRIMINATOR,
+            ),
+            ("CardGameClosedEvent", CardGameClosedEvent::DISCRIMINATOR),
+            ("HandChangedEvent", HandChangedEvent::DISCRIMINATOR),
+        ];
+
+        for (name, discriminator) in events {
+            let expected = &hash(format!("event:{name}").as_bytes()).to_bytes()[..8];
+            assert_eq!(discriminator, expected, "{name}");
+        }
+    }
+
+    fn revealed_deck(previous_deck_commitment: Option<[u8; 32]>) -> DeckRevealedEvent {
+        let mut deck = [0u128; DECK_LIMBS];
+        deck[0] = 0x0123_4567_89ab_cdef;
+        deck[15] = 1 << 100;
+        let deck_salt = 0xfeed_beef_u128;
+
+        let mut message = previous_deck_commitment.map_or(Vec::new(), |previous| previous.to_vec());
+        message.extend_from_slice(&deck_salt.to_le_bytes());
+        for limb in deck {
+            message.extend_from_slice(&limb.to_le_bytes());
+        }
+
+        DeckRevealedEvent {
+            game_id: 1,
+            deck,
+            deck_salt,
+            deck_commitment: hash(&message).to_bytes(),
+            previous_deck_commitment,
+            deck_size: 52,
+            cards_dealt: 10,
+        }
+    }
+
+    #[test]
+    fn verifies_deck_commitment() {
+        let mut event = revealed_deck(None);
+        assert!(event.verify());
+        assert!(event.verify_chain(event.deck_commitment, &[]));
+
+        event.deck[3] ^= 1;
+        assert!(!event.verify());
+    }
+
+    #[test]
+    fn verifies_chained_deck_commitment() {
+        let shuffled = [7; 32];
+        let mut event = revealed_deck(Some(shuffled));
+        assert!(event.verify());
+
+        let reshuffled = DeckReshuffledEvent {
+            game_id: 1,
+            cards_remaining: 42,
+            deck_commitment: event.deck_commitment,
+            previous_deck_commitment: shuffled,
+        };
+        assert!(event.verify_chain(shuffled, std::slice::from_ref(&reshuffled)));
+        // The chain must start at the original shuffle and include the reshuffle
+        assert!(!event.verify_chain([8; 32], &[reshuffled]));
+        assert!(!event.verify_chain(shuffled, &[]));
+
+        // Dropping the link breaks the commitment
+        event.previous_deck_commitment = None;
+        assert!(!event.verify());
+    }
+
+    #[test]
+    fn parses_program_data() {
+        // PlayerStoodEvent { game_id: 7, seat: 2 } as logged by the program
+        let logs = [
+            format!("Program {PROGRAM} invoke [1]"),
+            "Program log: Instruction: PlayerStand".to_string(),
+            "Program data: htmPO5WHLEMHAAAAAAAAAAI=".to_string(),
+            format!("Program {PROGRAM} consumed 4213 of 200000 compute units"),
+            format!("Program {PROGRAM} success"),
+        ];
+
+        let events = parse_logs(&logs);
+        assert_eq!(
+            events,
+            [CardShufflerEvent::PlayerStood(PlayerStoodEvent {
+                game_id: 7,
+                seat: 2
+            })]
+        );
+        assert_eq!(events[0].game_id(), 7);
+    }
+
+    #[test]
+    fn skips_data_logged_by_other_programs() {
+        let other = Pubkey::new_unique();
+        let cleared = BoardClearedEvent { game_id: 3 };
+        let cancelled = ComputationCancelledEvent {
+            game_id: 3,
+            offset: 99,
+            kind: ComputationKind::ChangeHand,
+        };
+        let logs = [
+            format!("Program {other} invoke [1]"),
+            format!("Program data: {}", encode(&cleared)),
+            format!("Program {PROGRAM} invoke [2]"),
+            format!("Program data: {}", encode(&cancelled)),
+            format!("Program {other} invoke [3]"),
+            format!("Program data: {}", encode(&cleared)),
+            format!("Program {other} success"),
+            format!("Program data: {}", encode(&cleared)),
+            format!("Program {PROGRAM} success"),
+            format!("Program data: {}", encode(&cleared)),
+            format!("Program {other} success"),
+        ];
+
+        assert_eq!(
+            parse_logs(&logs),
+            [
+                CardShufflerEvent::ComputationCancelled(cancelled),
+                CardShufflerEvent::BoardCleared(cleared),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_or_malformed_data() {
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(&7u64.to_le_bytes());
+        assert_eq!(CardShufflerEvent::decode(&data), None);
+
+        // Known discriminator, truncated body
+        let mut data = PlayerStoodEvent::DISCRIMINATOR.to_vec();
+        data.push(7);
+        assert_eq!(CardShufflerEvent::decode(&data), None);
+        assert_eq!(CardShufflerEvent::decode(&[1, 2, 3]), None);
+
+        let logs = [
+            format!("Program {PROGRAM} invoke [1]"),
+            format!("Program data: {}", BASE64_STANDARD.encode([0u8; 16])),
+            "Program data: not base64!".to_string(),
+            format!("Program {PROGRAM} success"),
+        ];
+        assert_eq!(parse_logs(&logs), []);
+    }
+}
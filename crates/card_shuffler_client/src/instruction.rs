@@ -0,0 +1,1120 @@
+//! Instruction builders for the card_shuffler program, for bots and keepers
+//! that send transactions from Rust rather than through the TypeScript SDK.
+//!
+//! Every Arcium and card_shuffler account an instruction needs is derived with
+//! [`crate::pda`]; callers provide the signers, the game and the Arcium cluster.
+//! The `*_callback` instructions are sent by the Arcium cluster and have no
+//! builder. To register for a completion notification, append the consumer
+//! program and its accounts to the returned instruction's `accounts`, then send
+//! [`deliver_notification`] once the callback has landed (see
+//! [`crate::CardShufflerNotification`]).
+//!
+//! ```ignore
+//! let accounts = QueueAccounts { payer, authority, cluster_offset };
+//! let ix = instruction::store_hole_cards(&accounts, computation_offset, game_id, 0, 2, 0);
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::system_program;
+
+use crate::pda::{
+    cluster_pda, comp_def_offset, comp_def_pda, computation_pda, execpool_pda, mempool_pda,
+    mxe_pda, notifier_pda, sign_pda, ARCIUM_CLOCK_ACCOUNT, ARCIUM_FEE_POOL_ACCOUNT,
+    ARCIUM_PROGRAM_ID,
+};
+use crate::{
+    get_baccarat_round_pda, get_card_game_pda, DeckSpec, EntropySeed, CARD_SHUFFLER_PROGRAM_ID,
+};
+
+/// Anchor instruction discriminators of the card_shuffler program
+pub mod discriminators {
+    pub const INIT_SHUFFLE_AND_DEAL_COMP_DEF: [u8; 8] = [33, 127, 167, 36, 169, 181, 126, 113];
+    pub const INITIALIZE_CARD_GAME: [u8; 8] = [213, 230, 53, 218, 4, 28, 104, 226];
+    pub const INIT_STORE_HOLE_CARDS_COMP_DEF: [u8; 8] = [41, 200, 206, 181, 248, 33, 239, 79];
+    pub const STORE_HOLE_CARDS: [u8; 8] = [106, 170, 143, 170, 118, 98, 216, 254];
+    pub const INIT_REVEAL_COMMUNITY_COMP_DEF: [u8; 8] = [159, 23, 19, 225, 153, 199, 47, 195];
+    pub const REVEAL_COMMUNITY_CARDS: [u8; 8] = [116, 73, 53, 59, 215, 115, 30, 6];
+    pub const CLEAR_BOARD: [u8; 8] = [79, 116, 76, 38, 214, 137, 203, 100];
+    pub const INIT_CHANGE_HAND_COMP_DEF: [u8; 8] = [45, 25, 31, 161, 75, 166, 202, 207];
+    pub const CHANGE_HAND: [u8; 8] = [185, 121, 86, 9, 186, 72, 186, 228];
+    pub const INIT_SEAT_PLAYER_COMP_DEF: [u8; 8] = [9, 28, 185, 50, 172, 118, 217, 67];
+    pub const SEAT_PLAYER: [u8; 8] = [7, 38, 253, 140, 213, 3, 208, 119];
+    pub const INIT_REVEAL_HOLE_CARDS_COMP_DEF: [u8; 8] = [148, 22, 45, 202, 28, 186, 8, 4];
+    pub const REVEAL_HOLE_CARDS: [u8; 8] = [136, 205, 87, 122, 202, 221, 161, 94];
+    pub const INIT_RESOLVE_SHOWDOWN_COMP_DEF: [u8; 8] = [14, 89, 139, 47, 121, 32, 147, 160];
+    pub const RESOLVE_SHOWDOWN: [u8; 8] = [154, 165, 96, 136, 226, 99, 136, 199];
+    pub const INIT_DEAL_DEALER_HAND_COMP_DEF: [u8; 8] = [7, 196, 195, 232, 42, 107, 208, 199];
+    pub const DEAL_DEALER_HAND: [u8; 8] = [51, 93, 24, 3, 86, 0, 140, 230];
+    pub const INIT_PLAYER_HIT_COMP_DEF: [u8; 8] = [208, 197, 36, 224, 186, 107, 23, 82];
+    pub const PLAYER_HIT: [u8; 8] = [29, 3, 180, 101, 8, 106, 205, 34];
+    pub const PLAYER_STAND: [u8; 8] = [128, 194, 19, 170, 132, 217, 87, 255];
+    pub const TRANSFER_AUTHORITY: [u8; 8] = [48, 169, 76, 72, 229, 180, 55, 161];
+    pub const CANCEL_PENDING_COMPUTATION: [u8; 8] = [134, 135, 254, 116, 103, 111, 126, 220];
+    pub const CLOSE_CARD_GAME: [u8; 8] = [55, 75, 11, 147, 155, 26, 252, 66];
+    pub const INIT_DEALER_PLAY_COMP_DEF: [u8; 8] = [184, 224, 158, 161, 244, 84, 236, 31];
+    pub const DEALER_PLAY: [u8; 8] = [231, 236, 27, 254, 217, 70, 48, 134];
+    pub const INIT_RESHUFFLE_REMAINING_COMP_DEF: [u8; 8] = [230, 245, 133, 91, 27, 253, 190, 58];
+    pub const RESHUFFLE_REMAINING: [u8; 8] = [94, 199, 132, 70, 29, 219, 140, 90];
+    pub const INIT_DISCARD_AND_DRAW_COMP_DEF: [u8; 8] = [125, 133, 200, 111, 207, 220, 80, 236];
+    pub const DISCARD_AND_DRAW: [u8; 8] = [82, 29, 120, 99, 200, 16, 19, 49];
+    pub const INIT_REVEAL_DECK_COMP_DEF: [u8; 8] = [166, 122, 151, 91, 38, 187, 167, 43];
+    pub const REVEAL_DECK: [u8; 8] = [151, 106, 151, 10, 124, 70, 251, 44];
+    pub const SET_HOUSE_KEY: [u8; 8] = [235, 96, 14, 51, 36, 62, 40, 166];
+    pub const INIT_DEAL_HOUSE_CARDS_COMP_DEF: [u8; 8] = [120, 140, 0, 3, 166, 138, 116, 145];
+    pub const DEAL_HOUSE_CARDS: [u8; 8] = [149, 197, 29, 198, 94, 209, 124, 238];
+    pub const INIT_REVEAL_HOUSE_CARDS_COMP_DEF: [u8; 8] = [70, 133, 166, 40, 213, 200, 102, 76];
+    pub const REVEAL_HOUSE_CARDS: [u8; 8] = [45, 227, 74, 30, 12, 121, 67, 23];
+    pub const INIT_PLAY_BACCARAT_COMP_DEF: [u8; 8] = [207, 141, 110, 30, 187, 11, 238, 100];
+    pub const PLAY_BACCARAT: [u8; 8] = [9, 38, 15, 226, 0, 247, 221, 44];
+    pub const CLOSE_BACCARAT_ROUND: [u8; 8] = [149, 132, 124, 211, 191, 77, 231, 9];
+    pub const INIT_PASS_CARDS_COMP_DEF: [u8; 8] = [111, 82, 126, 49, 149, 193, 148, 170];
+    pub const PASS_CARDS: [u8; 8] = [71, 234, 121, 163, 60, 218, 118, 82];
+    pub const DELIVER_NOTIFICATION: [u8; 8] = [89, 50, 140, 202, 16, 193, 239, 148];
+}
+
+/// Names of the card_shuffler encrypted instructions, from which the
+/// computation definition offsets are derived (see [`comp_def_offset`])
+pub mod encrypted_ixs {
+    pub const SHUFFLE_AND_DEAL_DECK: &str = "shuffle_and_deal_deck";
+    pub const STORE_HOLE_CARDS: &str = "store_hole_cards";
+    pub const REVEAL_COMMUNITY_CARDS: &str = "reveal_community_cards";
+    pub const CHANGE_HAND: &str = "change_hand";
+    pub const SEAT_PLAYER: &str = "seat_player";
+    pub const REVEAL_HOLE_CARDS: &str = "reveal_hole_cards";
+    pub const RESOLVE_SHOWDOWN: &str = "resolve_showdown";
+    pub const DEAL_DEALER_HAND: &str = "deal_dealer_hand";
+    pub const PLAYER_HIT: &str = "player_hit";
+    pub const DEALER_PLAY: &str = "dealer_play";
+    pub const RESHUFFLE_REMAINING: &str = "reshuffle_remaining";
+    pub const DISCARD_AND_DRAW: &str = "discard_and_draw";
+    pub const REVEAL_DECK: &str = "reveal_deck";
+    pub const DEAL_HOUSE_CARDS: &str = "deal_house_cards";
+    pub const REVEAL_HOUSE_CARDS: &str = "reveal_house_cards";
+    pub const PLAY_BACCARAT: &str = "play_baccarat";
+    pub const PASS_CARDS: &str = "pass_cards";
+}
+
+/// Signers and Arcium cluster of an instruction that queues a computation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueueAccounts {
+    /// Pays for the computation and, on `initialize_card_game`, the game account
+    pub payer: Pubkey,
+    /// The game's authority
+    pub authority: Pubkey,
+    /// Offset of the Arcium cluster the MXE is assigned to
+    pub cluster_offset: u32,
+}
+
+impl QueueAccounts {
+    /// Account metas in the order card_shuffler's queue instructions expect them
+    fn metas(&self, encrypted_ix: &str, computation_offset: u64, game_id: u64) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(sign_pda().0, false),
+            AccountMeta::new_readonly(mxe_pda(), false),
+            AccountMeta::new(mempool_pda(), false),
+            AccountMeta::new(execpool_pda(), false),
+            AccountMeta::new(computation_pda(computation_offset), false),
+            AccountMeta::new_readonly(comp_def_pda(comp_def_offset(encrypted_ix)), false),
+            AccountMeta::new(cluster_pda(self.cluster_offset), false),
+            AccountMeta::new(ARCIUM_FEE_POOL_ACCOUNT, false),
+            AccountMeta::new_readonly(ARCIUM_CLOCK_ACCOUNT, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(ARCIUM_PROGRAM_ID, false),
+            AccountMeta::new(get_card_game_pda(game_id).0, false),
+        ]
+    }
+}
+
+pub fn init_shuffle_and_deal_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_SHUFFLE_AND_DEAL_COMP_DEF,
+        encrypted_ixs::SHUFFLE_AND_DEAL_DECK,
+    )
+}
+
+pub fn init_store_hole_cards_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_STORE_HOLE_CARDS_COMP_DEF,
+        encrypted_ixs::STORE_HOLE_CARDS,
+    )
+}
+
+pub fn init_reveal_community_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_REVEAL_COMMUNITY_COMP_DEF,
+        encrypted_ixs::REVEAL_COMMUNITY_CARDS,
+    )
+}
+
+pub fn init_change_hand_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_CHANGE_HAND_COMP_DEF,
+        encrypted_ixs::CHANGE_HAND,
+    )
+}
+
+pub fn init_seat_player_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_SEAT_PLAYER_COMP_DEF,
+        encrypted_ixs::SEAT_PLAYER,
+    )
+}
+
+pub fn init_reveal_hole_cards_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_REVEAL_HOLE_CARDS_COMP_DEF,
+        encrypted_ixs::REVEAL_HOLE_CARDS,
+    )
+}
+
+pub fn init_resolve_showdown_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_RESOLVE_SHOWDOWN_COMP_DEF,
+        encrypted_ixs::RESOLVE_SHOWDOWN,
+    )
+}
+
+pub fn init_deal_dealer_hand_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_DEAL_DEALER_HAND_COMP_DEF,
+        encrypted_ixs::DEAL_DEALER_HAND,
+    )
+}
+
+pub fn init_player_hit_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_PLAYER_HIT_COMP_DEF,
+        encrypted_ixs::PLAYER_HIT,
+    )
+}
+
+pub fn init_dealer_play_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_DEALER_PLAY_COMP_DEF,
+        encrypted_ixs::DEALER_PLAY,
+    )
+}
+
+pub fn init_reshuffle_remaining_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_RESHUFFLE_REMAINING_COMP_DEF,
+        encrypted_ixs::RESHUFFLE_REMAINING,
+    )
+}
+
+pub fn init_discard_and_draw_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_DISCARD_AND_DRAW_COMP_DEF,
+        encrypted_ixs::DISCARD_AND_DRAW,
+    )
+}
+
+pub fn init_reveal_deck_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_REVEAL_DECK_COMP_DEF,
+        encrypted_ixs::REVEAL_DECK,
+    )
+}
+
+pub fn init_deal_house_cards_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_DEAL_HOUSE_CARDS_COMP_DEF,
+        encrypted_ixs::DEAL_HOUSE_CARDS,
+    )
+}
+
+pub fn init_reveal_house_cards_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_REVEAL_HOUSE_CARDS_COMP_DEF,
+        encrypted_ixs::REVEAL_HOUSE_CARDS,
+    )
+}
+
+pub fn init_play_baccarat_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_PLAY_BACCARAT_COMP_DEF,
+        encrypted_ixs::PLAY_BACCARAT,
+    )
+}
+
+pub fn init_pass_cards_comp_def(payer: Pubkey) -> Instruction {
+    init_comp_def(
+        payer,
+        discriminators::INIT_PASS_CARDS_COMP_DEF,
+        encrypted_ixs::PASS_CARDS,
+    )
+}
+
+/// Creates a card game, shuffles the deck and deals `num_hole_cards` to seat 0.
+/// `accounts.authority` becomes the game's authority. The board holds up to
+/// `max_community_cards` (at most [`crate::MAX_COMMUNITY_CARDS`]). `salt_nonce`
+/// encrypts the deck commitment salt and must differ from `mxe_nonce`. Up to
+/// [`crate::MAX_ENTROPY_SEEDS`] `entropy_seeds` are mixed into the shuffle
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_card_game(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    mxe_nonce: u128,
+    salt_nonce: u128,
+    client_pubkey: [u8; 32],
+    client_nonce: u128,
+    num_hole_cards: u8,
+    deck_spec: DeckSpec,
+    max_community_cards: u8,
+    entropy_seeds: &[EntropySeed],
+) -> Instruction {
+    instruction(
+        discriminators::INITIALIZE_CARD_GAME,
+        accounts.metas(
+            encrypted_ixs::SHUFFLE_AND_DEAL_DECK,
+            computation_offset,
+            game_id,
+        ),
+        (
+            computation_offset,
+            game_id,
+            mxe_nonce,
+            salt_nonce,
+            client_pubkey,
+            client_nonce,
+            num_hole_cards,
+            deck_spec,
+            max_community_cards,
+            entropy_seeds,
+        ),
+    )
+}
+
+/// Deals `num_new_cards` more hole cards to `seat`, after burning `num_burn`
+pub fn store_hole_cards(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    seat: u8,
+    num_new_cards: u8,
+    num_burn: u8,
+) -> Instruction {
+    instruction(
+        discriminators::STORE_HOLE_CARDS,
+        accounts.metas(encrypted_ixs::STORE_HOLE_CARDS, computation_offset, game_id),
+        (computation_offset, game_id, seat, num_new_cards, num_burn),
+    )
+}
+
+/// Reveals the next `num_cards_to_reveal` community cards, after burning `num_burn`
+pub fn reveal_community_cards(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    num_cards_to_reveal: u8,
+    num_burn: u8,
+) -> Instruction {
+    instruction(
+        discriminators::REVEAL_COMMUNITY_CARDS,
+        accounts.metas(
+            encrypted_ixs::REVEAL_COMMUNITY_CARDS,
+            computation_offset,
+            game_id,
+        ),
+        (computation_offset, game_id, num_cards_to_reveal, num_burn),
+    )
+}
+
+/// Replaces `seat`'s hand with a fresh empty one encrypted under `new_nonce`
+pub fn change_hand(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    seat: u8,
+    new_nonce: u128,
+) -> Instruction {
+    instruction(
+        discriminators::CHANGE_HAND,
+        accounts.metas(encrypted_ixs::CHANGE_HAND, computation_offset, game_id),
+        (computation_offset, game_id, seat, new_nonce),
+    )
+}
+
+/// Seats `player_pubkey` at the next free seat and deals them `num_hole_cards`,
+/// encrypted to `client_pubkey`
+pub fn seat_player(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    player_pubkey: Pubkey,
+    client_pubkey: [u8; 32],
+    client_nonce: u128,
+    num_hole_cards: u8,
+) -> Instruction {
+    instruction(
+        discriminators::SEAT_PLAYER,
+        accounts.metas(encrypted_ixs::SEAT_PLAYER, computation_offset, game_id),
+        (
+            computation_offset,
+            game_id,
+            player_pubkey,
+            client_pubkey,
+            client_nonce,
+            num_hole_cards,
+        ),
+    )
+}
+
+/// Reveals the hole cards of `seat` selected by `reveal_mask` (bit i for slot i)
+pub fn reveal_hole_cards(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    seat: u8,
+    reveal_mask: u16,
+) -> Instruction {
+    instruction(
+        discriminators::REVEAL_HOLE_CARDS,
+        accounts.metas(
+            encrypted_ixs::REVEAL_HOLE_CARDS,
+            computation_offset,
+            game_id,
+        ),
+        (computation_offset, game_id, seat, reveal_mask),
+    )
+}
+
+/// Finds the best hold'em hand among the seats in the `contenders` bitmask
+pub fn resolve_showdown(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    contenders: u16,
+) -> Instruction {
+    instruction(
+        discriminators::RESOLVE_SHOWDOWN,
+        accounts.metas(encrypted_ixs::RESOLVE_SHOWDOWN, computation_offset, game_id),
+        (computation_offset, game_id, contenders),
+    )
+}
+
+/// Deals the blackjack dealer two cards, revealing the upcard. Fails while the
+/// previous dealer hand has not been played
+pub fn deal_dealer_hand(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    mxe_nonce: u128,
+) -> Instruction {
+    instruction(
+        discriminators::DEAL_DEALER_HAND,
+        accounts.metas(encrypted_ixs::DEAL_DEALER_HAND, computation_offset, game_id),
+        (computation_offset, game_id, mxe_nonce),
+    )
+}
+
+/// Deals one more blackjack card to `seat`, between `deal_dealer_hand` and
+/// `dealer_play`
+pub fn player_hit(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    seat: u8,
+) -> Instruction {
+    instruction(
+        discriminators::PLAYER_HIT,
+        accounts.metas(encrypted_ixs::PLAYER_HIT, computation_offset, game_id),
+        (computation_offset, game_id, seat),
+    )
+}
+
+/// Plays out the blackjack dealer's hand and settles every seat
+pub fn dealer_play(accounts: &QueueAccounts, computation_offset: u64, game_id: u64) -> Instruction {
+    instruction(
+        discriminators::DEALER_PLAY,
+        accounts.metas(encrypted_ixs::DEALER_PLAY, computation_offset, game_id),
+        (computation_offset, game_id),
+    )
+}
+
+/// Reshuffles the cards that have not been dealt yet, mixing in up to
+/// [`crate::MAX_ENTROPY_SEEDS`] `entropy_seeds`
+pub fn reshuffle_remaining(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    entropy_seeds: &[EntropySeed],
+) -> Instruction {
+    instruction(
+        discriminators::RESHUFFLE_REMAINING,
+        accounts.metas(
+            encrypted_ixs::RESHUFFLE_REMAINING,
+            computation_offset,
+            game_id,
+        ),
+        (computation_offset, game_id, entropy_seeds),
+    )
+}
+
+/// Discards the cards of `seat` selected by `discard_mask` (bit i for slot i)
+/// and draws as many replacements
+pub fn discard_and_draw(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    seat: u8,
+    discard_mask: u16,
+) -> Instruction {
+    instruction(
+        discriminators::DISCARD_AND_DRAW,
+        accounts.metas(encrypted_ixs::DISCARD_AND_DRAW, computation_offset, game_id),
+        (computation_offset, game_id, seat, discard_mask),
+    )
+}
+
+/// Publishes the deck and its commitment salt; no more cards can be dealt afterwards.
+/// Every hand must be empty and any blackjack dealer hand played out first
+pub fn reveal_deck(accounts: &QueueAccounts, computation_offset: u64, game_id: u64) -> Instruction {
+    instruction(
+        discriminators::REVEAL_DECK,
+        accounts.metas(encrypted_ixs::REVEAL_DECK, computation_offset, game_id),
+        (computation_offset, game_id),
+    )
+}
+
+/// Deals `num_new_cards` to the house seat, after burning `num_burn`. The hand
+/// is re-encrypted to the house key under `house_nonce`, which must be fresh
+pub fn deal_house_cards(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    house_nonce: u128,
+    num_new_cards: u8,
+    num_burn: u8,
+) -> Instruction {
+    instruction(
+        discriminators::DEAL_HOUSE_CARDS,
+        accounts.metas(encrypted_ixs::DEAL_HOUSE_CARDS, computation_offset, game_id),
+        (
+            computation_offset,
+            game_id,
+            house_nonce,
+            num_new_cards,
+            num_burn,
+        ),
+    )
+}
+
+/// Reveals the house cards selected by `reveal_mask` (bit i for slot i)
+pub fn reveal_house_cards(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    reveal_mask: u16,
+) -> Instruction {
+    instruction(
+        discriminators::REVEAL_HOUSE_CARDS,
+        accounts.metas(
+            encrypted_ixs::REVEAL_HOUSE_CARDS,
+            computation_offset,
+            game_id,
+        ),
+        (computation_offset, game_id, reveal_mask),
+    )
+}
+
+/// Plays a baccarat coup from the shoe; the hands, totals and outcome are
+/// written to the game's BaccaratRound account (see [`get_baccarat_round_pda`])
+pub fn play_baccarat(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+) -> Instruction {
+    let mut metas = accounts.metas(encrypted_ixs::PLAY_BACCARAT, computation_offset, game_id);
+    metas.push(AccountMeta::new(get_baccarat_round_pda(game_id).0, false));
+    instruction(
+        discriminators::PLAY_BACCARAT,
+        metas,
+        (computation_offset, game_id),
+    )
+}
+
+/// Passes `num_cards` cards from `sender_seat` to `recipient_seat`. The sender
+/// selects them with a u16 slot mask (bit i for slot i) encrypted with their
+/// shared secret under `selection_nonce`, e.g.
+/// `cipher.encrypt(&[mask as u128], selection_nonce)[0]`
+#[allow(clippy::too_many_arguments)]
+pub fn pass_cards(
+    accounts: &QueueAccounts,
+    computation_offset: u64,
+    game_id: u64,
+    sender_seat: u8,
+    recipient_seat: u8,
+    num_cards: u8,
+    selection_nonce: u128,
+    selection_ciphertext: [u8; 32],
+) -> Instruction {
+    instruction(
+        discriminators::PASS_CARDS,
+        accounts.metas(encrypted_ixs::PASS_CARDS, computation_offset, game_id),
+        (
+            computation_offset,
+            game_id,
+            sender_seat,
+            recipient_seat,
+            num_cards,
+            selection_nonce,
+            selection_ciphertext,
+        ),
+    )
+}
+
+/// Assigns the house seat's x25519 key and empties its hand
+pub fn set_house_key(authority: Pubkey, game_id: u64, house_enc_pubkey: [u8; 32]) -> Instruction {
+    instruction(
+        discriminators::SET_HOUSE_KEY,
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(get_card_game_pda(game_id).0, false),
+        ],
+        (game_id, house_enc_pubkey),
+    )
+}
+
+/// Clears the community cards for the next hand
+pub fn clear_board(authority: Pubkey, game_id: u64) -> Instruction {
+    instruction(
+        discriminators::CLEAR_BOARD,
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(get_card_game_pda(game_id).0, false),
+        ],
+        game_id,
+    )
+}
+
+/// Marks `seat` as standing in the current blackjack round
+pub fn player_stand(authority: Pubkey, game_id: u64, seat: u8) -> Instruction {
+    instruction(
+        discriminators::PLAYER_STAND,
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(get_card_game_pda(game_id).0, false),
+        ],
+        (game_id, seat),
+    )
+}
+
+/// Hands the game over to `new_authority`
+pub fn transfer_authority(authority: Pubkey, game_id: u64, new_authority: Pubkey) -> Instruction {
+    instruction(
+        discriminators::TRANSFER_AUTHORITY,
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(get_card_game_pda(game_id).0, false),
+        ],
+        (game_id, new_authority),
+    )
+}
+
+/// Releases the game from a computation whose callback never landed; allowed
+/// once [`crate::COMPUTATION_TIMEOUT_SLOTS`] have passed since it was queued
+pub fn cancel_pending_computation(authority: Pubkey, game_id: u64) -> Instruction {
+    instruction(
+        discriminators::CANCEL_PENDING_COMPUTATION,
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(get_card_game_pda(game_id).0, false),
+        ],
+        game_id,
+    )
+}
+
+/// Closes the game account, sending its rent to `destination`
+pub fn close_card_game(authority: Pubkey, game_id: u64, destination: Pubkey) -> Instruction {
+    instruction(
+        discriminators::CLOSE_CARD_GAME,
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(get_card_game_pda(game_id).0, false),
+        ],
+        game_id,
+    )
+}
+
+/// Delivers the notification the last callback recorded to `consumer_program`,
+/// along with `forwarded`, the accounts registered with it at queue time. Anyone
+/// can send it
+pub fn deliver_notification(
+    game_id: u64,
+    consumer_program: Pubkey,
+    forwarded: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(get_card_game_pda(game_id).0, false),
+        AccountMeta::new_readonly(notifier_pda().0, false),
+        AccountMeta::new_readonly(consumer_program, false),
+    ];
+    accounts.extend_from_slice(forwarded);
+    instruction(discriminators::DELIVER_NOTIFICATION, accounts, game_id)
+}
+
+/// Closes the game's BaccaratRound account, sending its rent to `destination`
+pub fn close_baccarat_round(authority: Pubkey, game_id: u64, destination: Pubkey) -> Instruction {
+    instruction(
+        discriminators::CLOSE_BACCARAT_ROUND,
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(get_card_game_pda(game_id).0, false),
+            AccountMeta::new(get_baccarat_round_pda(game_id).0, false),
+        ],
+        game_id,
+    )
+}
+
+/// Creates the computation definition account of `encrypted_ix`
+fn init_comp_def(payer: Pubkey, discriminator: [u8; 8], encrypted_ix: &str) -> Instruction {
+    instruction(
+        discriminator,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(mxe_pda(), false),
+            AccountMeta::new(comp_def_pda(comp_def_offset(encrypted_ix)), false),
+            AccountMeta::new_readonly(ARCIUM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        (),
+    )
+}
+
+/// Serializes the instruction arguments after the discriminator
+fn instruction(
+    discriminator: [u8; 8],
+    accounts: Vec<AccountMeta>,
+    args: impl AnchorSerialize,
+) -> Instruction {
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data)
+        .expect("serializing into a Vec cannot fail");
+
+    Instruction {
+        program_id: CARD_SHUFFLER_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::solana_program::hash::hash;
+
+    use super::*;
+
+    const GAME_ID: u64 = 7;
+    const OFFSET: u64 = 42;
+
+    fn queue_accounts() -> QueueAccounts {
+        QueueAccounts {
+            payer: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            cluster_offset: 3,
+        }
+    }
+
+    /// Every builder, by the name of the program instruction it calls
+    fn builders(accounts: &QueueAccounts) -> Vec<(&'static str, Instruction)> {
+        let payer = accounts.payer;
+        let authority = accounts.authority;
+        let other = Pubkey::new_unique();
+        let a = accounts;
+        vec![
+            (
+                "init_shuffle_and_deal_comp_def",
+                init_shuffle_and_deal_comp_def(payer),
+            ),
+            (
+                "init_store_hole_cards_comp_def",
+                init_store_hole_cards_comp_def(payer),
+            ),
+            (
+                "init_reveal_community_comp_def",
+                init_reveal_community_comp_def(payer),
+            ),
+            (
+                "init_change_hand_comp_def",
+                init_change_hand_comp_def(payer),
+            ),
+            (
+                "init_seat_player_comp_def",
+                init_seat_player_comp_def(payer),
+            ),
+            (
+                "init_reveal_hole_cards_comp_def",
+                init_reveal_hole_cards_comp_def(payer),
+            ),
+            (
+                "init_resolve_showdown_comp_def",
+                init_resolve_showdown_comp_def(payer),
+            ),
+            (
+                "init_deal_dealer_hand_comp_def",
+                init_deal_dealer_hand_comp_def(payer),
+            ),
+            ("init_player_hit_comp_def", init_player_hit_comp_def(payer)),
+            (
+                "init_dealer_play_comp_def",
+                init_dealer_play_comp_def(payer),
+            ),
+            (
+                "init_reshuffle_remaining_comp_def",
+                init_reshuffle_remaining_comp_def(payer),
+            ),
+            (
+                "init_discard_and_draw_comp_def",
+                init_discard_and_draw_comp_def(payer),
+            ),
+            (
+                "init_reveal_deck_comp_def",
+                init_reveal_deck_comp_def(payer),
+            ),
+            (
+                "init_deal_house_cards_comp_def",
+                init_deal_house_cards_comp_def(payer),
+            ),
+            (
+                "init_reveal_house_cards_comp_def",
+                init_reveal_house_cards_comp_def(payer),
+            ),
+            (
+                "init_play_baccarat_comp_def",
+                init_play_baccarat_comp_def(payer),
+            ),
+            ("init_pass_cards_comp_def", init_pass_cards_comp_def(payer)),
+            (
+                "initialize_card_game",
+                initialize_card_game(
+                    a,
+                    OFFSET,
+                    GAME_ID,
+                    1,
+                    2,
+                    [3; 32],
+                    4,
+                    2,
+                    DeckSpec::STANDARD,
+                    5,
+                    &[],
+                ),
+            ),
+            (
+                "store_hole_cards",
+                store_hole_cards(a, OFFSET, GAME_ID, 0, 2, 0),
+            ),
+            (
+                "reveal_community_cards",
+                reveal_community_cards(a, OFFSET, GAME_ID, 3, 1),
+            ),
+            ("change_hand", change_hand(a, OFFSET, GAME_ID, 0, 9)),
+            (
+                "seat_player",
+                seat_player(a, OFFSET, GAME_ID, other, [3; 32], 4, 2),
+            ),
+            (
+                "reveal_hole_cards",
+                reveal_hole_cards(a, OFFSET, GAME_ID, 0, 0b11),
+            ),
+            (
+                "resolve_showdown",
+                resolve_showdown(a, OFFSET, GAME_ID, 0b11),
+            ),
+            ("deal_dealer_hand", deal_dealer_hand(a, OFFSET, GAME_ID, 9)),
+            ("player_hit", player_hit(a, OFFSET, GAME_ID, 0)),
+            ("dealer_play", dealer_play(a, OFFSET, GAME_ID)),
+            (
+                "reshuffle_remaining",
+                reshuffle_remaining(a, OFFSET, GAME_ID, &[]),
+            ),
+            (
+                "discard_and_draw",
+                discard_and_draw(a, OFFSET, GAME_ID, 0, 0b101),
+            ),
+            ("reveal_deck", reveal_deck(a, OFFSET, GAME_ID)),
+            (
+                "deal_house_cards",
+                deal_house_cards(a, OFFSET, GAME_ID, 9, 2, 0),
+            ),
+            (
+                "reveal_house_cards",
+                reveal_house_cards(a, OFFSET, GAME_ID, 0b1),
+            ),
+            ("play_baccarat", play_baccarat(a, OFFSET, GAME_ID)),
+            (
+                "pass_cards",
+                pass_cards(a, OFFSET, GAME_ID, 0, 1, 3, 9, [8; 32]),
+            ),
+            ("set_house_key", set_house_key(authority, GAME_ID, [3; 32])),
+            ("clear_board", clear_board(authority, GAME_ID)),
+            ("player_stand", player_stand(authority, GAME_ID, 0)),
+            (
+                "transfer_authority",
+                transfer_authority(authority, GAME_ID, other),
+            ),
+            (
+                "cancel_pending_computation",
+                cancel_pending_computation(authority, GAME_ID),
+            ),
+            (
+                "close_card_game",
+                close_card_game(authority, GAME_ID, other),
+            ),
+            (
+                "close_baccarat_round",
+                close_baccarat_round(authority, GAME_ID, other),
+            ),
+            (
+                "deliver_notification",
+                deliver_notification(GAME_ID, other, &[]),
+            ),
+        ]
+    }
+
+    /// (is_signer, is_writable) of each account
+    fn flags(ix: &Instruction) -> Vec<(bool, bool)> {
+        ix.accounts
+            .iter()
+            .map(|meta| (meta.is_signer, meta.is_writable))
+            .collect()
+    }
+
+    #[test]
+    fn builders_use_anchor_discriminators() {
+        let builders = builders(&queue_accounts());
+        // One builder per discriminator
+        assert_eq!(builders.len(), 42);
+        for (name, ix) in builders {
+            let expected = &hash(format!("global:{name}").as_bytes()).to_bytes()[..8];
+            assert_eq!(&ix.data[..8], expected, "{name}");
+            assert_eq!(ix.program_id, CARD_SHUFFLER_PROGRAM_ID, "{name}");
+        }
+    }
+
+    #[test]
+    fn queue_builders_list_the_arcium_accounts() {
+        let accounts = queue_accounts();
+        let expected_flags = [
+            (true, true),
+            (true, false),
+            (false, true),
+            (false, false),
+            (false, true),
+            (false, true),
+            (false, true),
+            (false, false),
+            (false, true),
+            (false, true),
+            (false, false),
+            (false, false),
+            (false, false),
+            (false, true),
+        ];
+
+        let ix = store_hole_cards(&accounts, OFFSET, GAME_ID, 0, 2, 0);
+        assert_eq!(flags(&ix), expected_flags);
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            keys,
+            [
+                accounts.payer,
+                accounts.authority,
+                sign_pda().0,
+                mxe_pda(),
+                mempool_pda(),
+                execpool_pda(),
+                computation_pda(OFFSET),
+                comp_def_pda(comp_def_offset(encrypted_ixs::STORE_HOLE_CARDS)),
+                cluster_pda(3),
+                ARCIUM_FEE_POOL_ACCOUNT,
+                ARCIUM_CLOCK_ACCOUNT,
+                system_program::ID,
+                ARCIUM_PROGRAM_ID,
+                get_card_game_pda(GAME_ID).0,
+            ]
+        );
+
+        // Each queue builder points at its own computation definition
+        let shuffle = initialize_card_game(
+            &accounts,
+            OFFSET,
+            GAME_ID,
+            1,
+            2,
+            [3; 32],
+            4,
+            2,
+            DeckSpec::STANDARD,
+            5,
+            &[],
+        );
+        assert_eq!(flags(&shuffle), expected_flags);
+        assert_eq!(
+            shuffle.accounts[7].pubkey,
+            comp_def_pda(comp_def_offset(encrypted_ixs::SHUFFLE_AND_DEAL_DECK))
+        );
+
+        // play_baccarat also writes the round account
+        let baccarat = play_baccarat(&accounts, OFFSET, GAME_ID);
+        assert_eq!(flags(&baccarat)[..14], expected_flags);
+        assert_eq!(
+            baccarat.accounts[14],
+            AccountMeta::new(get_baccarat_round_pda(GAME_ID).0, false)
+        );
+        assert_eq!(
+            baccarat.accounts[7].pubkey,
+            comp_def_pda(comp_def_offset(encrypted_ixs::PLAY_BACCARAT))
+        );
+    }
+
+    #[test]
+    fn authority_builders_list_their_accounts() {
+        let authority = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let card_game = get_card_game_pda(GAME_ID).0;
+
+        for ix in [
+            set_house_key(authority, GAME_ID, [3; 32]),
+            clear_board(authority, GAME_ID),
+            player_stand(authority, GAME_ID, 0),
+            transfer_authority(authority, GAME_ID, destination),
+            cancel_pending_computation(authority, GAME_ID),
+        ] {
+            assert_eq!(
+                ix.accounts,
+                [
+                    AccountMeta::new_readonly(authority, true),
+                    AccountMeta::new(card_game, false),
+                ]
+            );
+        }
+
+        assert_eq!(
+            close_card_game(authority, GAME_ID, destination).accounts,
+            [
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new(destination, false),
+                AccountMeta::new(card_game, false),
+            ]
+        );
+        assert_eq!(
+            close_baccarat_round(authority, GAME_ID, destination).accounts,
+            [
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new(destination, false),
+                AccountMeta::new_readonly(card_game, false),
+                AccountMeta::new(get_baccarat_round_pda(GAME_ID).0, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn deliver_notification_forwards_the_consumer_accounts() {
+        let consumer_program = Pubkey::new_unique();
+        let forwarded = [
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        ];
+
+        let ix = deliver_notification(GAME_ID, consumer_program, &forwarded);
+        assert_eq!(
+            ix.accounts,
+            [
+                AccountMeta::new(get_card_game_pda(GAME_ID).0, false),
+                AccountMeta::new_readonly(notifier_pda().0, false),
+                AccountMeta::new_readonly(consumer_program, false),
+                forwarded[0].clone(),
+                forwarded[1].clone(),
+            ]
+        );
+        assert_eq!(ix.data[8..], GAME_ID.to_le_bytes());
+    }
+
+    #[test]
+    fn init_comp_def_builders_list_their_accounts() {
+        let payer = Pubkey::new_unique();
+        assert_eq!(
+            init_reveal_deck_comp_def(payer).accounts,
+            [
+                AccountMeta::new(payer, true),
+                AccountMeta::new(mxe_pda(), false),
+                AccountMeta::new(
+                    comp_def_pda(comp_def_offset(encrypted_ixs::REVEAL_DECK)),
+                    false
+                ),
+                AccountMeta::new_readonly(ARCIUM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_initialize_card_game_arguments() {
+        let seed = EntropySeed {
+            enc_pubkey: [5; 32],
+            nonce: 6,
+            ciphertext: [7; 32],
+        };
+        let deck_spec = DeckSpec {
+            rank_mask: 0x1ff0,
+            suit_mask: 0xf,
+            copies: 2,
+            jokers: 1,
+        };
+        let ix = initialize_card_game(
+            &queue_accounts(),
+            OFFSET,
+            GAME_ID,
+            1,
+            2,
+            [3; 32],
+            4,
+            2,
+            deck_spec,
+            5,
+            &[seed],
+        );
+
+        let mut expected = discriminators::INITIALIZE_CARD_GAME.to_vec();
+        expected.extend_from_slice(&OFFSET.to_le_bytes());
+        expected.extend_from_slice(&GAME_ID.to_le_bytes());
+        expected.extend_from_slice(&1u128.to_le_bytes()); // mxe_nonce
+        expected.extend_from_slice(&2u128.to_le_bytes()); // salt_nonce
+        expected.extend_from_slice(&[3; 32]); // client_pubkey
+        expected.extend_from_slice(&4u128.to_le_bytes()); // client_nonce
+        expected.push(2); // num_hole_cards
+        expected.extend_from_slice(&0x1ff0u16.to_le_bytes());
+        expected.extend_from_slice(&[0xf, 2, 1]); // suit_mask, copies, jokers
+        expected.push(5); // max_community_cards
+        expected.extend_from_slice(&1u32.to_le_bytes()); // entropy_seeds length
+        expected.extend_from_slice(&[5; 32]);
+        expected.extend_from_slice(&6u128.to_le_bytes());
+        expected.extend_from_slice(&[7; 32]);
+
+        assert_eq!(ix.data, expected);
+    }
+}
@@ -1,12 +1,29 @@
 use anchor_lang::prelude::*;
 
+pub mod card;
+pub mod cpi;
+#[cfg(feature = "decrypt")]
+pub mod crypto;
+pub mod events;
+pub mod instruction;
+pub mod pda;
+pub mod poker;
+
+pub use card::{Card, Rank, Suit};
+
 /// The card_shuffler program ID
 /// This must match the ID in the deployed card_shuffler program
 pub const CARD_SHUFFLER_PROGRAM_ID: Pubkey = solana_program::pubkey!("DQxanaqqWcTYvVhrKbeoY6q52NrGksWBL6vSbuVipnS7");
 
+pub const MAX_SEATS: usize = 10;
+pub const MAX_DECK_COPIES: u8 = 6;
+pub const DECK_LIMBS: usize = 16;
 pub const MAX_HOLE_CARDS: usize = 11;
 pub const MAX_COMMUNITY_CARDS: usize = 5;
 pub const EMPTY_CARD_MARKER: u8 = 53;
+pub const MAX_ENTROPY_SEEDS: usize = 4;
+/// Slots after queueing before `cancel_pending_computation` is allowed
+pub const COMPUTATION_TIMEOUT_SLOTS: u64 = 1_500;
 
 /// Helper function to derive the CardGame PDA from the card_shuffler program
 pub fn get_card_game_pda(game_id: u64) -> (Pubkey, u8) {
@@ -17,48 +34,307 @@ pub fn get_card_game_pda(game_id: u64) -> (Pubkey, u8) {
     )
 }
 
+/// Helper function to derive a game's BaccaratRound PDA
+pub fn get_baccarat_round_pda(game_id: u64) -> (Pubkey, u8) {
+    let game_id_bytes = game_id.to_le_bytes();
+    Pubkey::find_program_address(
+        &[b"baccarat", game_id_bytes.as_ref()],
+        &CARD_SHUFFLER_PROGRAM_ID,
+    )
+}
+
 /// Represents a card game session with encrypted deck and hands.
 /// This is the main account managed by the card_shuffler program.
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct CardGame {
-    /// Encrypted deck split into 3 chunks (52 cards encoded in base-64)
-    pub deck: [[u8; 32]; 3],
+    /// Encrypted deck split into 16 chunks (up to 324 cards encoded in base-64)
+    pub deck: [[u8; 32]; DECK_LIMBS],
     /// Cryptographic nonce for deck encryption
     pub deck_nonce: u128,
-    /// Player's encrypted hole cards
-    pub hole_cards: [u8; 32],
-    /// Cryptographic nonce for hole cards encryption
-    pub hole_cards_nonce: u128,
-    /// Number of hole cards currently held
-    pub hole_cards_size: u8,
+    /// Blackjack dealer's hand, encrypted to the MXE only
+    pub dealer_hand: [u8; 32],
+    /// Cryptographic nonce for dealer hand encryption
+    pub dealer_hand_nonce: u128,
+    /// Discarded cards, encrypted to the MXE only and packed like the deck
+    pub discard_pile: [[u8; 32]; DECK_LIMBS],
+    /// Cryptographic nonce for discard pile encryption
+    pub discard_pile_nonce: u128,
+    /// Salt of the deck commitment, encrypted to the MXE only until `reveal_deck`
+    pub deck_salt: [u8; 32],
+    /// Cryptographic nonce for deck salt encryption
+    pub deck_salt_nonce: u128,
+    /// Seats dealt from this deck, each with its own encrypted hand
+    pub seats: [Seat; MAX_SEATS],
+    /// House (dealer) seat of house-banked games, encrypted to the house key in
+    /// `player_enc_pubkey` rather than to any player
+    pub house: Seat,
+    /// Number of occupied seats
+    pub num_seats: u8,
     /// Revealed community cards (plaintext)
     pub community_cards: [u8; 5],
     /// Number of community cards revealed
     pub community_cards_size: u8,
+    /// Most community cards this game's board holds
+    pub max_community_cards: u8,
     /// Total number of cards dealt from the deck
-    pub cards_dealt: u8,
+    pub cards_dealt: u16,
+    /// Number of cards in the discard pile
+    pub discard_pile_size: u16,
+    /// Number of cards burned (skipped face down, never revealed)
+    pub cards_burned: u16,
     /// Unique identifier for this game session
     pub game_id: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Bitmask of the seats that won the last showdown
+    pub showdown_winners: u16,
+    /// Category of the winning showdown hand
+    /// (0 high card, 1 pair, 2 two pair, 3 trips, 4 straight,
+    /// 5 flush, 6 full house, 7 quads, 8 straight flush)
+    pub showdown_category: u8,
+    /// Number of cards in the dealer's hand
+    pub dealer_hand_size: u8,
+    /// Dealer's face-up card (plaintext)
+    pub dealer_upcard: u8,
+    /// Dealer's full hand, revealed once the dealer has played
+    pub dealer_cards: [u8; MAX_HOLE_CARDS],
+    /// Dealer's final blackjack total
+    pub dealer_total: u8,
+    /// Whether the dealer has played out the current dealer hand
+    pub dealer_played: bool,
+    /// Blackjack result for each seat
+    /// (0 not playing, 1 lose, 2 push, 3 win, 4 blackjack)
+    pub blackjack_outcomes: [u8; MAX_SEATS],
+    /// Composition of the deck this game was shuffled from
+    pub deck_spec: DeckSpec,
+    /// Number of cards in the deck
+    pub deck_size: u16,
+    /// Whether the shuffle callback has populated the deck
+    pub deck_shuffled: bool,
+    /// SHA-256 of the commitment salt followed by the packed deck limbs (each as
+    /// 16 little-endian bytes), set by every shuffle of the deck. After a
+    /// reshuffle the hash is prefixed with `previous_deck_commitment` too
+    pub deck_commitment: [u8; 32],
+    /// Commitment the current one is chained to, if the deck has been reshuffled
+    pub previous_deck_commitment: Option<[u8; 32]>,
+    /// Whether `reveal_deck` has published the deck; no more cards can be dealt
+    pub deck_revealed: bool,
+    /// Computation queued on this game that has not called back yet
+    pub pending_computation: Option<PendingComputation>,
+    /// Account allowed to mutate the game (its creator or a delegated game program)
+    pub authority: Pubkey,
+    /// Notification the last callback recorded for a consumer program, waiting
+    /// for `deliver_notification`
+    pub pending_notification: Option<PendingNotification>,
+}
+
+/// Computation queued on a game, awaiting its callback
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct PendingComputation {
+    /// Offset the computation was queued with
+    pub offset: u64,
+    pub kind: ComputationKind,
+    /// Slot the computation was queued in
+    pub queued_slot: u64,
+}
+
+impl PendingComputation {
+    /// First slot at which the authority may cancel the computation
+    pub fn cancellable_at_slot(&self) -> u64 {
+        self.queued_slot + COMPUTATION_TIMEOUT_SLOTS
+    }
+}
+
+/// Notification recorded by a callback, waiting for `deliver_notification`
+/// (see [`instruction::deliver_notification`])
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct PendingNotification {
+    /// Program whose `on_card_shuffler_callback` receives the notification
+    pub consumer_program: Pubkey,
+    /// SHA-256 of the forwarded accounts' keys, each followed by a writable flag
+    /// byte; a delivery must pass the same accounts
+    pub accounts_hash: [u8; 32],
+    pub notification: CardShufflerNotification,
+}
+
+/// Encrypted instruction a computation ran
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum ComputationKind {
+    ShuffleAndDeal,
+    StoreHoleCards,
+    RevealCommunityCards,
+    ChangeHand,
+    SeatPlayer,
+    RevealHoleCards,
+    ResolveShowdown,
+    DealDealerHand,
+    PlayerHit,
+    DealerPlay,
+    ReshuffleRemaining,
+    DiscardAndDraw,
+    RevealDeck,
+    DealHouseCards,
+    RevealHouseCards,
+    PlayBaccarat,
+    PassCards,
+}
+
+/// Argument of the `on_card_shuffler_callback` instruction card_shuffler invokes on a
+/// consumer program once a computation the consumer registered for completes.
+///
+/// The instruction receives the card_shuffler notifier PDA (see [`pda::notifier_pda`])
+/// as a signer, then the CardGame, then the accounts forwarded at queue time.
+///
+/// The callback only records the notification in
+/// [`CardGame::pending_notification`]; it reaches the consumer when anyone sends
+/// [`instruction::deliver_notification`]. A consumer that fails only fails that
+/// delivery, which can be retried, and never the game's update. A notification
+/// that was not delivered is replaced by the next one recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct CardShufflerNotification {
+    pub game_id: u64,
+    pub kind: ComputationKind,
+    /// False if the computation was aborted
+    pub success: bool,
+}
+
+/// A player's contribution to a shuffle: a random u128 encrypted with the
+/// player's own x25519 key (see `EntropySeed::encrypt` with the `decrypt` feature)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct EntropySeed {
+    /// x25519 public key the seed was encrypted with
+    pub enc_pubkey: [u8; 32],
+    pub nonce: u128,
+    pub ciphertext: [u8; 32],
+}
+
+/// Describes which cards make up a deck
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DeckSpec {
+    /// Bit r includes rank r (0 = two ... 12 = ace)
+    pub rank_mask: u16,
+    /// Bit s includes suit s (0 clubs, 1 diamonds, 2 hearts, 3 spades)
+    pub suit_mask: u8,
+    /// Number of copies of the selected cards (1 for a single deck, 6 for a six-deck shoe)
+    pub copies: u8,
+    /// Number of jokers added to the deck (card index 52), at most two per copy
+    pub jokers: u8,
+}
+
+impl DeckSpec {
+    /// Standard 52-card deck
+    pub const STANDARD: DeckSpec = DeckSpec {
+        rank_mask: 0x1fff,
+        suit_mask: 0xf,
+        copies: 1,
+        jokers: 0,
+    };
+
+    /// Short-deck hold'em: sixes through aces (36 cards)
+    pub const SHORT_DECK: DeckSpec = DeckSpec {
+        rank_mask: 0x1ff0,
+        suit_mask: 0xf,
+        copies: 1,
+        jokers: 0,
+    };
+
+    /// Euchre: nines through aces (24 cards)
+    pub const EUCHRE: DeckSpec = DeckSpec {
+        rank_mask: 0x1f80,
+        suit_mask: 0xf,
+        copies: 1,
+        jokers: 0,
+    };
+
+    /// Pinochle: two copies of nines through aces (48 cards)
+    pub const PINOCHLE: DeckSpec = DeckSpec {
+        rank_mask: 0x1f80,
+        suit_mask: 0xf,
+        copies: 2,
+        jokers: 0,
+    };
+
+    /// Six-deck blackjack shoe (312 cards)
+    pub const SIX_DECK_SHOE: DeckSpec = DeckSpec {
+        rank_mask: 0x1fff,
+        suit_mask: 0xf,
+        copies: 6,
+        jokers: 0,
+    };
+
+    /// Whether the spec describes a non-empty deck that fits in the packed representation
+    pub fn is_valid(&self) -> bool {
+        self.rank_mask != 0
+            && self.rank_mask >> 13 == 0
+            && self.suit_mask != 0
+            && self.suit_mask >> 4 == 0
+            && self.copies >= 1
+            && self.copies <= MAX_DECK_COPIES
+            && self.jokers <= 2 * self.copies
+    }
+
+    /// Number of cards in the deck
+    pub fn deck_size(&self) -> u16 {
+        (self.rank_mask.count_ones() * self.suit_mask.count_ones()) as u16 * self.copies as u16
+            + self.jokers as u16
+    }
+}
+
+/// A single player's position at the table
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub struct Seat {
+    /// Player's encrypted hole cards
+    pub hole_cards: [u8; 32],
+    /// Cryptographic nonce for hole cards encryption
+    pub hole_cards_nonce: u128,
+    /// Number of hole cards currently held
+    pub hole_cards_size: u8,
     /// Solana public key of the player
     pub player_pubkey: Pubkey,
     /// Player's encryption public key for MPC operations
     pub player_enc_pubkey: [u8; 32],
-    /// PDA bump seed
-    pub bump: u8,
+    /// Hole cards revealed at showdown (plaintext)
+    pub revealed_cards: [u8; MAX_HOLE_CARDS],
+    /// Number of hole cards revealed
+    pub revealed_cards_size: u8,
+    /// Whether the player has stood in the current blackjack round
+    pub stood: bool,
+    /// Whether the player's blackjack hand went over 21
+    pub busted: bool,
+}
+
+impl CardGame {
+    /// Returns the occupied seats in seat order
+    pub fn occupied_seats(&self) -> &[Seat] {
+        &self.seats[..(self.num_seats as usize).min(MAX_SEATS)]
+    }
+
+    /// Number of cards still undealt in the deck
+    pub fn cards_remaining(&self) -> u16 {
+        self.deck_size.saturating_sub(self.cards_dealt)
+    }
+
+    /// The revealed community cards, in the order they were dealt
+    pub fn board(&self) -> Vec<Card> {
+        let size = (self.community_cards_size as usize).min(MAX_COMMUNITY_CARDS);
+        card::cards_from_slots(&self.community_cards[..size])
+    }
 }
 
 impl anchor_lang::AccountSerialize for CardGame {
     fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> anchor_lang::Result<()> {
-        AnchorSerialize::serialize(self, writer)
-            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotSerialize.into())
+        serialize_account(self, writer)
     }
 }
 
 impl anchor_lang::AccountDeserialize for CardGame {
+    fn try_deserialize(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+        deserialize_account(buf, true)
+    }
+
     fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
-        AnchorDeserialize::deserialize(buf)
-            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+        deserialize_account(buf, false)
     }
 }
 
@@ -69,5 +345,215 @@ impl anchor_lang::Owner for CardGame {
 }
 
 impl anchor_lang::Discriminator for CardGame {
-    const DISCRIMINATOR: &'static [u8] = &[197, 251, 174, 61, 185, 100, 214, 215];
+    const DISCRIMINATOR: &'static [u8] = &[154, 253, 33, 68, 82, 220, 16, 89];
+}
+
+/// Baccarat coups played from a game's shoe; holds the result of the last one
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct BaccaratRound {
+    /// Game whose shoe the coups are dealt from
+    pub game_id: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Number of coups played
+    pub coups_played: u32,
+    /// Player's cards (plaintext, empty card marker past the hand size)
+    pub player_cards: [u8; 3],
+    /// Number of Player cards (2 or 3)
+    pub player_cards_size: u8,
+    /// Banker's cards (plaintext, empty card marker past the hand size)
+    pub banker_cards: [u8; 3],
+    /// Number of Banker cards (2 or 3)
+    pub banker_cards_size: u8,
+    /// Final Player total (0-9)
+    pub player_total: u8,
+    /// Final Banker total (0-9)
+    pub banker_total: u8,
+    /// Outcome of the last coup (0 none yet, 1 Player wins, 2 Banker wins, 3 tie)
+    pub outcome: u8,
+}
+
+impl BaccaratRound {
+    /// The Player's cards of the last coup, in the order they were dealt
+    pub fn player_hand(&self) -> Vec<Card> {
+        let size = (self.player_cards_size as usize).min(self.player_cards.len());
+        card::cards_from_slots(&self.player_cards[..size])
+    }
+
+    /// The Banker's cards of the last coup, in the order they were dealt
+    pub fn banker_hand(&self) -> Vec<Card> {
+        let size = (self.banker_cards_size as usize).min(self.banker_cards.len());
+        card::cards_from_slots(&self.banker_cards[..size])
+    }
+}
+
+impl anchor_lang::AccountSerialize for BaccaratRound {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> anchor_lang::Result<()> {
+        serialize_account(self, writer)
+    }
+}
+
+impl anchor_lang::AccountDeserialize for BaccaratRound {
+    fn try_deserialize(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+        deserialize_account(buf, true)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+        deserialize_account(buf, false)
+    }
+}
+
+impl anchor_lang::Owner for BaccaratRound {
+    fn owner() -> Pubkey {
+        CARD_SHUFFLER_PROGRAM_ID
+    }
+}
+
+impl anchor_lang::Discriminator for BaccaratRound {
+    const DISCRIMINATOR: &'static [u8] = &[219, 94, 225, 94, 181, 84, 140, 255];
+}
+
+/// Writes an account the way Anchor lays it out: the discriminator, then the
+/// Borsh encoding of its fields
+fn serialize_account<T: Discriminator + AnchorSerialize, W: std::io::Write>(
+    account: &T,
+    writer: &mut W,
+) -> anchor_lang::Result<()> {
+    writer
+        .write_all(T::DISCRIMINATOR)
+        .and_then(|_| account.serialize(writer))
+        .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotSerialize.into())
+}
+
+/// Reads an account written by the card_shuffler program, skipping its
+/// discriminator (and checking it if `check_discriminator`). Trailing bytes
+/// past the fields are ignored
+fn deserialize_account<T: Discriminator + AnchorDeserialize>(
+    buf: &[u8],
+    check_discriminator: bool,
+) -> anchor_lang::Result<T> {
+    let (discriminator, mut data) = buf
+        .split_at_checked(T::DISCRIMINATOR.len())
+        .ok_or(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound)?;
+    if check_discriminator && discriminator != T::DISCRIMINATOR {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    T::deserialize(&mut data)
+        .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::error::ErrorCode;
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::{AccountDeserialize, AccountSerialize};
+
+    use super::*;
+
+    // Byte offsets the program reads CardGame fields at (see its DECK_OFFSET etc.)
+    const DECK_OFFSET: usize = 8;
+    const DEALER_HAND_OFFSET: usize = DECK_OFFSET + 32 * DECK_LIMBS + 16;
+    const DISCARD_PILE_OFFSET: usize = DEALER_HAND_OFFSET + 32 + 16;
+    const DECK_SALT_OFFSET: usize = DISCARD_PILE_OFFSET + 32 * DECK_LIMBS + 16;
+    const SEATS_OFFSET: usize = DECK_SALT_OFFSET + 32 + 16;
+    const SEAT_LEN: usize = 127;
+    const HOUSE_HAND_OFFSET: usize = SEATS_OFFSET + MAX_SEATS * SEAT_LEN;
+
+    /// A CardGame account as the program stores it, with marker bytes in the
+    /// fields at known offsets and zeroed space after the fields
+    fn card_game_account() -> Vec<u8> {
+        let mut data = vec![0u8; 4096];
+        data[..8].copy_from_slice(CardGame::DISCRIMINATOR);
+        data[DECK_OFFSET..][..32].fill(1);
+        data[DEALER_HAND_OFFSET..][..32].fill(2);
+        data[DISCARD_PILE_OFFSET..][..32].fill(3);
+        data[DECK_SALT_OFFSET..][..32].fill(4);
+        data[SEATS_OFFSET + SEAT_LEN..][..32].fill(5);
+        data[HOUSE_HAND_OFFSET..][..32].fill(6);
+        data[HOUSE_HAND_OFFSET + SEAT_LEN] = 2; // num_seats
+        data
+    }
+
+    #[test]
+    fn discriminators_match_account_names() {
+        for (name, discriminator) in [
+            ("CardGame", CardGame::DISCRIMINATOR),
+            ("BaccaratRound", BaccaratRound::DISCRIMINATOR),
+        ] {
+            let expected = &hash(format!("account:{name}").as_bytes()).to_bytes()[..8];
+            assert_eq!(discriminator, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn reads_card_game_at_program_offsets() {
+        let data = card_game_account();
+        let card_game = CardGame::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(card_game.deck[0], [1; 32]);
+        assert_eq!(card_game.deck[1], [0; 32]);
+        assert_eq!(card_game.dealer_hand, [2; 32]);
+        assert_eq!(card_game.discard_pile[0], [3; 32]);
+        assert_eq!(card_game.deck_salt, [4; 32]);
+        assert_eq!(card_game.seats[1].hole_cards, [5; 32]);
+        assert_eq!(card_game.house.hole_cards, [6; 32]);
+        assert_eq!(card_game.num_seats, 2);
+        assert_eq!(card_game.pending_computation, None);
+
+        // Writing it back reproduces the program's layout
+        let mut written = Vec::new();
+        card_game.try_serialize(&mut written).unwrap();
+        assert_eq!(written, data[..written.len()]);
+    }
+
+    #[test]
+    fn checks_the_discriminator() {
+        let mut data = card_game_account();
+        data[0] ^= 1;
+        assert_eq!(
+            CardGame::try_deserialize(&mut data.as_slice()).err(),
+            Some(ErrorCode::AccountDiscriminatorMismatch.into())
+        );
+        // The unchecked read still skips it
+        let card_game = CardGame::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+        assert_eq!(card_game.deck[0], [1; 32]);
+
+        assert_eq!(
+            CardGame::try_deserialize(&mut &data[..4]).err(),
+            Some(ErrorCode::AccountDiscriminatorNotFound.into())
+        );
+        assert_eq!(
+            CardGame::try_deserialize(&mut &card_game_account()[..100]).err(),
+            Some(ErrorCode::AccountDidNotDeserialize.into())
+        );
+    }
+
+    #[test]
+    fn baccarat_round_round_trips() {
+        let round = BaccaratRound {
+            game_id: 7,
+            bump: 254,
+            coups_played: 3,
+            player_cards: [51, 12, EMPTY_CARD_MARKER],
+            player_cards_size: 2,
+            banker_cards: [0, 1, 2],
+            banker_cards_size: 3,
+            player_total: 1,
+            banker_total: 9,
+            outcome: 2,
+        };
+
+        let mut data = Vec::new();
+        round.try_serialize(&mut data).unwrap();
+        assert_eq!(&data[..8], BaccaratRound::DISCRIMINATOR);
+        assert_eq!(data[8..16], 7u64.to_le_bytes());
+
+        // The program's account has no padding, but tolerate it like Anchor does
+        data.extend_from_slice(&[0; 16]);
+        assert_eq!(
+            BaccaratRound::try_deserialize(&mut data.as_slice()).ok(),
+            Some(round)
+        );
+        assert!(BaccaratRound::try_deserialize(&mut card_game_account().as_slice()).is_err());
+    }
 }
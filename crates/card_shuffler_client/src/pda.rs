@@ -0,0 +1,96 @@
+//! Addresses of the card_shuffler program's PDAs and the Arcium accounts
+//! its instructions need.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::CARD_SHUFFLER_PROGRAM_ID;
+
+/// The Arcium program ID
+pub const ARCIUM_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6");
+/// Arcium fee pool account
+pub const ARCIUM_FEE_POOL_ACCOUNT: Pubkey =
+    solana_program::pubkey!("7MGSS4iKNM4sVib7bDZDJhVqB6EcchPwVnTKenCY1jt3");
+/// Arcium clock account
+pub const ARCIUM_CLOCK_ACCOUNT: Pubkey =
+    solana_program::pubkey!("FHriyvoZotYiFnbUzKFjzRSb2NiaC8RPWY7jtKuKhg65");
+
+/// Offset of a computation definition, derived from its encrypted instruction name
+/// (first four bytes of the SHA-256 digest, little endian)
+pub fn comp_def_offset(name: &str) -> u32 {
+    let digest = hash(name.as_bytes()).to_bytes();
+    u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// Signer PDA the card_shuffler program uses to queue computations
+pub fn sign_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"SignerAccount"], &CARD_SHUFFLER_PROGRAM_ID)
+}
+
+/// PDA that signs card_shuffler's notifications to consumer programs
+pub fn notifier_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"notifier"], &CARD_SHUFFLER_PROGRAM_ID)
+}
+
+/// MXE account of the card_shuffler program
+pub fn mxe_pda() -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"MXEAccount", CARD_SHUFFLER_PROGRAM_ID.as_ref()],
+        &ARCIUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Mempool account of the card_shuffler program
+pub fn mempool_pda() -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"Mempool", CARD_SHUFFLER_PROGRAM_ID.as_ref()],
+        &ARCIUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Executing pool account of the card_shuffler program
+pub fn execpool_pda() -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"Execpool", CARD_SHUFFLER_PROGRAM_ID.as_ref()],
+        &ARCIUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Account tracking the computation queued at `computation_offset`
+pub fn computation_pda(computation_offset: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"ComputationAccount",
+            CARD_SHUFFLER_PROGRAM_ID.as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        &ARCIUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Computation definition account for the encrypted instruction at `comp_def_offset`
+pub fn comp_def_pda(comp_def_offset: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"ComputationDefinitionAccount",
+            CARD_SHUFFLER_PROGRAM_ID.as_ref(),
+            &comp_def_offset.to_le_bytes(),
+        ],
+        &ARCIUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Cluster account at `cluster_offset` (as recorded on the MXE account)
+pub fn cluster_pda(cluster_offset: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"Cluster", &cluster_offset.to_le_bytes()],
+        &ARCIUM_PROGRAM_ID,
+    )
+    .0
+}
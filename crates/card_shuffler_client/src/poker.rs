@@ -0,0 +1,404 @@
+//! Off-chain poker hand evaluation.
+//!
+//! [`evaluate`] ranks the best five-card hand that can be made from 5, 6 or 7
+//! cards, the way `resolve_showdown` does in MPC. The resulting [`HandRank`]s
+//! compare with `Ord`: a greater rank wins and equal ranks split the pot.
+//!
+//! ```ignore
+//! let hole_cards = card_game.seats[0].decrypt_hole_cards(&cipher)?;
+//! let rank = card_game.evaluate_hand(&hole_cards)?;
+//! println!("{} ({:?})", rank.category(), rank.ranks());
+//! ```
+
+use std::fmt;
+
+use crate::card::{Card, Rank};
+use crate::CardGame;
+
+/// Hand category, low to high. The discriminants match
+/// `CardGame::showdown_category`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    pub const ALL: [HandCategory; 9] = [
+        HandCategory::HighCard,
+        HandCategory::Pair,
+        HandCategory::TwoPair,
+        HandCategory::ThreeOfAKind,
+        HandCategory::Straight,
+        HandCategory::Flush,
+        HandCategory::FullHouse,
+        HandCategory::FourOfAKind,
+        HandCategory::StraightFlush,
+    ];
+
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_index(index: u8) -> Option<HandCategory> {
+        HandCategory::ALL.get(index as usize).copied()
+    }
+
+    /// Number of leading ranks in a [`HandRank`] that make up the category;
+    /// the rest are kickers
+    fn made_ranks(self) -> usize {
+        match self {
+            HandCategory::HighCard
+            | HandCategory::Pair
+            | HandCategory::ThreeOfAKind
+            | HandCategory::FourOfAKind
+            | HandCategory::Straight
+            | HandCategory::StraightFlush => 1,
+            HandCategory::TwoPair | HandCategory::FullHouse => 2,
+            HandCategory::Flush => 5,
+        }
+    }
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandCategory::HighCard => "high card",
+            HandCategory::Pair => "pair",
+            HandCategory::TwoPair => "two pair",
+            HandCategory::ThreeOfAKind => "three of a kind",
+            HandCategory::Straight => "straight",
+            HandCategory::Flush => "flush",
+            HandCategory::FullHouse => "full house",
+            HandCategory::FourOfAKind => "four of a kind",
+            HandCategory::StraightFlush => "straight flush",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Strength of a five-card poker hand: its category followed by the ranks that
+/// break ties within the category, most significant first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HandRank {
+    category: HandCategory,
+    ranks: [Rank; 5],
+    len: u8,
+}
+
+impl HandRank {
+    fn new(category: HandCategory, ranks: &[Rank]) -> HandRank {
+        let mut padded = [Rank::Two; 5];
+        padded[..ranks.len()].copy_from_slice(ranks);
+        HandRank {
+            category,
+            ranks: padded,
+            len: ranks.len() as u8,
+        }
+    }
+
+    pub fn category(&self) -> HandCategory {
+        self.category
+    }
+
+    /// Ranks compared after the category, e.g. `[K, 7, A]` for kings and sevens
+    /// with an ace kicker, `[5]` for a five-high straight or `[Q, 4]` for queens
+    /// full of fours
+    pub fn ranks(&self) -> &[Rank] {
+        &self.ranks[..self.len as usize]
+    }
+
+    /// The ranks that are not part of the category itself (the `A` of kings and
+    /// sevens with an ace kicker). Empty for straights, flushes and full houses
+    pub fn kickers(&self) -> &[Rank] {
+        &self.ranks()[self.category.made_ranks()..]
+    }
+}
+
+impl fmt::Display for HandRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.category)?;
+        for (i, rank) in self.ranks().iter().enumerate() {
+            write!(f, "{}{rank}", if i == 0 { " " } else { "," })?;
+        }
+        Ok(())
+    }
+}
+
+/// Cards that cannot be evaluated as a poker hand
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvaluateError {
+    /// Hands are made from 5 to 7 cards
+    HandSize(usize),
+    /// Jokers are not wild in the evaluator
+    Joker,
+    /// The same card appears twice (e.g. a multi-deck shoe)
+    DuplicateCard(Card),
+}
+
+impl fmt::Display for EvaluateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvaluateError::HandSize(size) => write!(f, "expected 5 to 7 cards, got {size}"),
+            EvaluateError::Joker => write!(f, "jokers cannot be evaluated"),
+            EvaluateError::DuplicateCard(card) => write!(f, "{card} appears more than once"),
+        }
+    }
+}
+
+impl std::error::Error for EvaluateError {}
+
+/// Ranks the best five-card hand that can be made from 5, 6 or 7 distinct cards
+pub fn evaluate(cards: &[Card]) -> Result<HandRank, EvaluateError> {
+    if !(5..=7).contains(&cards.len()) {
+        return Err(EvaluateError::HandSize(cards.len()));
+    }
+
+    let mut seen = 0u64;
+    let mut suit_masks = [0u16; 4];
+    let mut counts = [0u8; 13];
+    for &card in cards {
+        let (Some(rank), Some(suit)) = (card.rank(), card.suit()) else {
+            return Err(EvaluateError::Joker);
+        };
+        let bit = 1u64 << card.index();
+        if seen & bit != 0 {
+            return Err(EvaluateError::DuplicateCard(card));
+        }
+        seen |= bit;
+        suit_masks[suit.index() as usize] |= 1 << rank.index();
+        counts[rank.index() as usize] += 1;
+    }
+
+    Ok(rank_hand(&suit_masks, &counts))
+}
+
+impl CardGame {
+    /// Ranks a player's best hand from their hole cards and the revealed board
+    pub fn evaluate_hand(&self, hole_cards: &[Card]) -> Result<HandRank, EvaluateError> {
+        let mut cards = self.board();
+        cards.extend_from_slice(hole_cards);
+        evaluate(&cards)
+    }
+}
+
+/// `suit_masks` holds a bit per rank for each suit and `counts` the number of
+/// cards of each rank, for at most seven distinct cards
+fn rank_hand(suit_masks: &[u16; 4], counts: &[u8; 13]) -> HandRank {
+    // With seven cards a flush leaves too few cards for quads or a full house,
+    // so a flush suit decides the hand on its own
+    if let Some(&flush) = suit_masks.iter().find(|mask| mask.count_ones() >= 5) {
+        return match straight_high(flush) {
+            Some(high) => HandRank::new(HandCategory::StraightFlush, &[high]),
+            None => HandRank::new(HandCategory::Flush, &top_ranks(flush, 5)),
+        };
+    }
+
+    let mut quads = None;
+    let mut trips: Vec<Rank> = Vec::new();
+    let mut pairs: Vec<Rank> = Vec::new();
+    for rank in Rank::ALL.into_iter().rev() {
+        match counts[rank.index() as usize] {
+            4 => quads = Some(rank),
+            3 => trips.push(rank),
+            2 => pairs.push(rank),
+            _ => {}
+        }
+    }
+
+    let all = suit_masks.iter().fold(0, |acc, mask| acc | mask);
+    let kickers = |used: &[Rank], n: usize| {
+        let used = used.iter().fold(0, |acc, rank| acc | 1 << rank.index());
+        top_ranks(all & !used, n)
+    };
+    let with_kickers = |category, made: &[Rank], n| {
+        let mut ranks = made.to_vec();
+        ranks.extend(kickers(made, n));
+        HandRank::new(category, &ranks)
+    };
+
+    if let Some(quads) = quads {
+        return with_kickers(HandCategory::FourOfAKind, &[quads], 1);
+    }
+    if let Some(&three) = trips.first() {
+        // A second set of trips plays as the pair
+        if let Some(&pair) = trips.get(1).into_iter().chain(&pairs).max() {
+            return HandRank::new(HandCategory::FullHouse, &[three, pair]);
+        }
+    }
+    if let Some(high) = straight_high(all) {
+        return HandRank::new(HandCategory::Straight, &[high]);
+    }
+    match (trips.first(), pairs.as_slice()) {
+        (Some(&three), _) => with_kickers(HandCategory::ThreeOfAKind, &[three], 2),
+        (None, [high, low, ..]) => with_kickers(HandCategory::TwoPair, &[*high, *low], 1),
+        (None, [pair]) => with_kickers(HandCategory::Pair, &[*pair], 3),
+        (None, []) => HandRank::new(HandCategory::HighCard, &top_ranks(all, 5)),
+    }
+}
+
+/// Highest card of the best straight in a rank mask, counting the ace low for
+/// the five-high straight (the wheel)
+fn straight_high(mask: u16) -> Option<Rank> {
+    // Bit 0 is the low ace and bit i + 1 is rank i
+    let shifted = (mask << 1) | ((mask >> Rank::Ace.index()) & 1);
+    (4..=13u8)
+        .rev()
+        .find(|&high| (shifted >> (high - 4)) & 0x1f == 0x1f)
+        .and_then(|high| Rank::from_index(high - 1))
+}
+
+/// The `n` highest ranks set in a rank mask
+fn top_ranks(mask: u16, n: usize) -> Vec<Rank> {
+    Rank::ALL
+        .into_iter()
+        .rev()
+        .filter(|rank| mask & (1 << rank.index()) != 0)
+        .take(n)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn cards(hand: &str) -> Vec<Card> {
+        hand.split_whitespace()
+            .map(|card| card.parse().unwrap())
+            .collect()
+    }
+
+    fn rank(hand: &str) -> HandRank {
+        evaluate(&cards(hand)).unwrap()
+    }
+
+    fn all_cards() -> Vec<Card> {
+        (0..52)
+            .map(|index| Card::try_from(index).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn every_five_card_hand() {
+        let deck = all_cards();
+        let mut counts = [0u32; 9];
+        let mut distinct = HashSet::new();
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let hand = [deck[a], deck[b], deck[c], deck[d], deck[e]];
+                            let rank = evaluate(&hand).unwrap();
+                            counts[rank.category().index() as usize] += 1;
+                            distinct.insert(rank);
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(
+            counts,
+            [1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 40]
+        );
+        assert_eq!(counts.iter().sum::<u32>(), 2_598_960);
+        // Every hand that can be told apart gets its own rank
+        assert_eq!(distinct.len(), 7_462);
+    }
+
+    #[test]
+    fn seven_cards_play_the_best_five() {
+        let deck = all_cards();
+        // Deterministic pseudo-random deals
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..2_000 {
+            let mut hand = Vec::new();
+            while hand.len() < 7 {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                let card = deck[(state % 52) as usize];
+                if !hand.contains(&card) {
+                    hand.push(card);
+                }
+            }
+
+            let mut best = None;
+            for skip_a in 0..7 {
+                for skip_b in skip_a + 1..7 {
+                    let five: Vec<Card> = (0..7)
+                        .filter(|&i| i != skip_a && i != skip_b)
+                        .map(|i| hand[i])
+                        .collect();
+                    best = best.max(Some(evaluate(&five).unwrap()));
+                }
+            }
+            assert_eq!(Some(evaluate(&hand).unwrap()), best, "{hand:?}");
+            assert_eq!(
+                evaluate(&hand[..6]).unwrap(),
+                (0..6)
+                    .map(|skip| {
+                        let five: Vec<Card> =
+                            (0..6).filter(|&i| i != skip).map(|i| hand[i]).collect();
+                        evaluate(&five).unwrap()
+                    })
+                    .max()
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn ranks_and_kickers() {
+        let wheel = rank("Ah 2c 3d 4s 5h Kc Kd");
+        assert_eq!(wheel.category(), HandCategory::Straight);
+        assert_eq!(wheel.ranks(), [Rank::Five]);
+        assert!(rank("2h 3c 4d 5s 6h") > wheel);
+
+        let two_pair = rank("Kh Kc 7d 7s 2h 2c Ad");
+        assert_eq!(two_pair.ranks(), [Rank::King, Rank::Seven, Rank::Ace]);
+        assert_eq!(two_pair.kickers(), [Rank::Ace]);
+
+        let full_house = rank("Qh Qc Qd 4s 4h 4c 9d");
+        assert_eq!(full_house.category(), HandCategory::FullHouse);
+        assert_eq!(full_house.ranks(), [Rank::Queen, Rank::Four]);
+        assert!(full_house.kickers().is_empty());
+
+        assert!(rank("Ah Ac 9d 8s 3h") > rank("Ad As 9c 8h 2d"));
+        assert_eq!(rank("Ah Ac 9d 8s 3h"), rank("Ad As 9c 8h 3d"));
+        assert!(rank("2h 3h 4h 5h 7h") > rank("Ah Kc Qd Js Th"));
+        assert!(rank("Ah 2h 3h 4h 5h") < rank("2c 3c 4c 5c 6c"));
+        assert_eq!(full_house.to_string(), "full house Q,4");
+    }
+
+    #[test]
+    fn rejects_invalid_hands() {
+        assert_eq!(
+            evaluate(&cards("Ah Kh Qh Jh")),
+            Err(EvaluateError::HandSize(4))
+        );
+        assert_eq!(
+            evaluate(&cards("Ah Kh Qh Jh Th 9h 8h 7h")),
+            Err(EvaluateError::HandSize(8))
+        );
+        assert_eq!(
+            evaluate(&cards("Ah Kh Qh Jh Jk")),
+            Err(EvaluateError::Joker)
+        );
+        assert_eq!(
+            evaluate(&cards("Ah Kh Qh Jh Ah")),
+            Err(EvaluateError::DuplicateCard("Ah".parse().unwrap()))
+        );
+    }
+}
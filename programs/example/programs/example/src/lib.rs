@@ -1,7 +1,10 @@
 // example of a poker game, doesnt actually work as one
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use card_shuffler_client::{
-    get_card_game_pda, CardGame, CARD_SHUFFLER_PROGRAM_ID,
+    cpi as card_shuffler_cpi, get_card_game_pda, pda::notifier_pda, CardGame,
+    CardShufflerNotification, ComputationKind, DeckSpec, CARD_SHUFFLER_PROGRAM_ID,
+    MAX_COMMUNITY_CARDS,
 };
 
 declare_id!("56Hf5PcrNpJ9z94MczM2iWymqP8oR7pxAtRirveQqCPv");
@@ -12,16 +15,31 @@ pub mod example {
 
     /// Create a poker game session
     /// consumes the card_shuffler_client crate
+    /// `shuffler_funding` lamports are moved to the session's shuffler authority PDA,
+    /// which pays for the card_shuffler computations it queues
     pub fn create_game_session(
         ctx: Context<CreateGameSession>,
         game_id: u64,
+        shuffler_funding: u64,
     ) -> Result<()> {
         let game_session = &mut ctx.accounts.game_session;
         game_session.game_id = game_id;
         game_session.player = ctx.accounts.player.key();
         game_session.bump = ctx.bumps.game_session;
+        game_session.shuffler_authority_bump = ctx.bumps.shuffler_authority;
         game_session.game_state = GameState::WaitingToShuffle;
 
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.shuffler_authority.to_account_info(),
+                },
+            ),
+            shuffler_funding,
+        )?;
+
         // lculate the expected card_game PDA from card_shuffler
         let (card_game_pda, _) = get_card_game_pda(game_id);
 
@@ -33,9 +51,17 @@ pub mod example {
         Ok(())
     }
 
-    /// Start the poker hand - marks game ready for card operations
-    /// will need to call arcium job
-    pub fn start_hand(ctx: Context<StartHand>) -> Result<()> {
+    /// Start the poker hand
+    /// The first hand creates the CardGame and shuffles the deck through a CPI into
+    /// card_shuffler; later hands keep dealing from the same deck
+    pub fn start_hand(
+        ctx: Context<StartHand>,
+        computation_offset: u64,
+        mxe_nonce: u128,
+        salt_nonce: u128,
+        client_pubkey: [u8; 32],
+        client_nonce: u128,
+    ) -> Result<()> {
         let game_session = &mut ctx.accounts.game_session;
 
         require!(
@@ -46,15 +72,42 @@ pub mod example {
         game_session.game_state = GameState::ShufflingDeck;
         game_session.hand_number += 1;
 
-        msg!("Hand #{} started - ready to shuffle", game_session.hand_number);
-        msg!("TypeScript should now call card_shuffler.shuffleAndDeal()");
+        msg!("Hand #{} started", game_session.hand_number);
+
+        if game_session.hand_number == 1 {
+            let game_id = game_session.game_id;
+            let session_key = game_session.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"shuffler_authority",
+                session_key.as_ref(),
+                &[game_session.shuffler_authority_bump],
+            ]];
+
+            card_shuffler_cpi::initialize_card_game(
+                CpiContext::new_with_signer(
+                    ctx.accounts.card_shuffler_program.to_account_info(),
+                    ctx.accounts.shuffler.to_cpi_accounts(),
+                    signer_seeds,
+                ),
+                computation_offset,
+                game_id,
+                mxe_nonce,
+                salt_nonce,
+                client_pubkey,
+                client_nonce,
+                0, // hole cards are dealt in deal_hole_cards
+                DeckSpec::STANDARD,
+                MAX_COMMUNITY_CARDS as u8,
+                &[], // no player seeds; the shuffle uses Arcium's randomness alone
+            )?;
+            msg!("Queued card_shuffler.initialize_card_game");
+        }
 
         Ok(())
     }
 
-    /// Deal hole cards - updates game state after shuffle completes
-    /// will need to call arcium job
-    pub fn deal_hole_cards(ctx: Context<DealHoleCards>) -> Result<()> {
+    /// Deal two hole cards to the player (seat 0) through a CPI into card_shuffler
+    pub fn deal_hole_cards(ctx: Context<DealHoleCards>, computation_offset: u64) -> Result<()> {
         let game_session = &mut ctx.accounts.game_session;
 
         require!(
@@ -64,8 +117,108 @@ pub mod example {
 
         game_session.game_state = GameState::HoleCardsDealt;
 
-        msg!("Deck shuffled - ready to deal hole cards");
-        msg!("TypeScript should now call card_shuffler.storeHoleCards()");
+        let game_id = game_session.game_id;
+        let session_key = game_session.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"shuffler_authority",
+            session_key.as_ref(),
+            &[game_session.shuffler_authority_bump],
+        ]];
+
+        card_shuffler_cpi::store_hole_cards(
+            CpiContext::new_with_signer(
+                ctx.accounts.card_shuffler_program.to_account_info(),
+                ctx.accounts.shuffler.to_cpi_accounts(),
+                signer_seeds,
+            ),
+            computation_offset,
+            game_id,
+            0,
+            2,
+            0,
+        )?;
+
+        msg!("Queued card_shuffler.store_hole_cards");
+
+        Ok(())
+    }
+
+    /// Queue the next community cards (flop, turn, or river) through a CPI
+    /// into card_shuffler. The game state advances in `on_card_shuffler_callback`
+    /// once the cards are revealed
+    pub fn deal_community_cards(
+        ctx: Context<DealCommunityCards>,
+        computation_offset: u64,
+        num_cards: u8,
+    ) -> Result<()> {
+        let game_session = &ctx.accounts.game_session;
+
+        require!(
+            game_session.game_state == GameState::HoleCardsDealt
+            || game_session.game_state == GameState::Flop
+            || game_session.game_state == GameState::Turn,
+            PokerError::WrongGameState
+        );
+
+        let game_id = game_session.game_id;
+        let session_key = game_session.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"shuffler_authority",
+            session_key.as_ref(),
+            &[game_session.shuffler_authority_bump],
+        ]];
+
+        card_shuffler_cpi::reveal_community_cards(
+            CpiContext::new_with_signer(
+                ctx.accounts.card_shuffler_program.to_account_info(),
+                ctx.accounts.shuffler.to_cpi_accounts(),
+                signer_seeds,
+            )
+            // Have card_shuffler call back into this program with the session
+            .with_remaining_accounts(vec![
+                ctx.accounts.example_program.to_account_info(),
+                ctx.accounts.game_session.to_account_info(),
+            ]),
+            computation_offset,
+            game_id,
+            num_cards,
+            // Burn one card before the flop, turn and river
+            1,
+        )?;
+
+        msg!("Queued card_shuffler.reveal_community_cards");
+
+        Ok(())
+    }
+
+    /// Called by card_shuffler's `deliver_notification` once a computation this
+    /// program registered for has updated the CardGame, so the game state
+    /// catches up with the cards
+    pub fn on_card_shuffler_callback(
+        ctx: Context<OnCardShufflerCallback>,
+        notification: CardShufflerNotification,
+    ) -> Result<()> {
+        if !notification.success {
+            msg!("card_shuffler computation {:?} aborted", notification.kind);
+            return Ok(());
+        }
+
+        // An error here would only fail the delivery and leave the notification
+        // pending for a retry that fails the same way, so an unexpected board is
+        // logged and ignored
+        if notification.kind == ComputationKind::RevealCommunityCards {
+            let game_session = &mut ctx.accounts.game_session;
+            game_session.game_state = match ctx.accounts.card_game.community_cards_size {
+                3 => GameState::Flop,
+                4 => GameState::Turn,
+                5 => GameState::River,
+                size => {
+                    msg!("Unexpected board of {} community cards", size);
+                    return Ok(());
+                }
+            };
+            msg!("Game state: {:?}", game_session.game_state);
+        }
 
         Ok(())
     }
@@ -95,8 +248,8 @@ pub mod example {
         };
 
         msg!("Community cards revealed:");
-        for i in 0..card_game.community_cards_size {
-            msg!("  Card {}: {}", i, card_game.community_cards[i as usize]);
+        for (i, card) in card_game.board().iter().enumerate() {
+            msg!("  Card {}: {}", i, card);
         }
         msg!("Game state: {:?}", game_session.game_state);
 
@@ -116,15 +269,18 @@ pub mod example {
         msg!("  Game State: {:?}", game_session.game_state);
 
         msg!("=== Card Game State (from card_shuffler) ===");
-        msg!("  Hole cards size: {}", card_game.hole_cards_size);
+        msg!("  Seats: {}", card_game.num_seats);
+        for (i, seat) in card_game.occupied_seats().iter().enumerate() {
+            msg!("    Seat {} hole cards size: {}", i, seat.hole_cards_size);
+        }
         msg!("  Community cards size: {}", card_game.community_cards_size);
         msg!("  Cards dealt: {}", card_game.cards_dealt);
 
         // Show community cards
         if card_game.community_cards_size > 0 {
             msg!("  Community cards:");
-            for i in 0..card_game.community_cards_size {
-                msg!("    Card {}: {}", i, card_game.community_cards[i as usize]);
+            for (i, card) in card_game.board().iter().enumerate() {
+                msg!("    Card {}: {}", i, card);
             }
         }
 
@@ -132,7 +288,7 @@ pub mod example {
             game_id: game_session.game_id,
             hand_number: game_session.hand_number,
             game_state: game_session.game_state,
-            hole_cards_size: card_game.hole_cards_size,
+            num_seats: card_game.num_seats,
             community_cards_size: card_game.community_cards_size,
             cards_dealt: card_game.cards_dealt,
         });
@@ -141,8 +297,12 @@ pub mod example {
     }
 
     /// End the current hand and reset for next hand
-    /// will need to call arcium job
-    pub fn end_hand(ctx: Context<EndHand>) -> Result<()> {
+    /// Clears the board and the player's hand through CPIs into card_shuffler
+    pub fn end_hand(
+        ctx: Context<EndHand>,
+        computation_offset: u64,
+        new_nonce: u128,
+    ) -> Result<()> {
         let game_session = &mut ctx.accounts.game_session;
 
         require!(
@@ -152,8 +312,41 @@ pub mod example {
 
         game_session.game_state = GameState::WaitingToShuffle;
 
-        msg!("Hand complete - ready for next hand");
-        msg!("TypeScript can call card_shuffler.changeHand() to deal new cards");
+        let game_id = game_session.game_id;
+        let session_key = game_session.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"shuffler_authority",
+            session_key.as_ref(),
+            &[game_session.shuffler_authority_bump],
+        ]];
+
+        // Clear the board first: it needs the game idle, and change_hand
+        // leaves a computation in flight
+        card_shuffler_cpi::clear_board(
+            CpiContext::new_with_signer(
+                ctx.accounts.card_shuffler_program.to_account_info(),
+                card_shuffler_cpi::accounts::UpdateGame {
+                    authority: ctx.accounts.shuffler.shuffler_authority.to_account_info(),
+                    card_game: ctx.accounts.shuffler.card_game.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            game_id,
+        )?;
+
+        card_shuffler_cpi::change_hand(
+            CpiContext::new_with_signer(
+                ctx.accounts.card_shuffler_program.to_account_info(),
+                ctx.accounts.shuffler.to_cpi_accounts(),
+                signer_seeds,
+            ),
+            computation_offset,
+            game_id,
+            0,
+            new_nonce,
+        )?;
+
+        msg!("Hand complete - board cleared, queued card_shuffler.change_hand");
 
         Ok(())
     }
@@ -174,6 +367,8 @@ pub struct GameSession {
     pub bump: u8,
     pub hand_number: u64,
     pub game_state: GameState,
+    /// Bump of the PDA that signs card_shuffler CPIs for this session
+    pub shuffler_authority_bump: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
@@ -201,19 +396,139 @@ pub struct CreateGameSession<'info> {
     )]
     pub game_session: Account<'info, GameSession>,
 
+    /// PDA that pays for and authorizes this session's card_shuffler computations
+    #[account(
+        mut,
+        seeds = [b"shuffler_authority", game_session.key().as_ref()],
+        bump,
+    )]
+    pub shuffler_authority: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts forwarded to a card_shuffler instruction that queues a computation.
+/// The Arcium accounts are validated by card_shuffler itself.
+#[derive(Accounts)]
+pub struct ShufflerAccounts<'info> {
+    /// Session PDA acting as card_shuffler payer and authority
+    #[account(mut)]
+    pub shuffler_authority: SystemAccount<'info>,
+    /// CHECK: sign_pda_account, checked by the card_shuffler program
+    #[account(mut)]
+    pub sign_pda_account: UncheckedAccount<'info>,
+    /// CHECK: mxe_account, checked by the card_shuffler program
+    pub mxe_account: UncheckedAccount<'info>,
+    /// CHECK: mempool_account, checked by the card_shuffler program
+    #[account(mut)]
+    pub mempool_account: UncheckedAccount<'info>,
+    /// CHECK: executing_pool, checked by the card_shuffler program
+    #[account(mut)]
+    pub executing_pool: UncheckedAccount<'info>,
+    /// CHECK: computation_account, checked by the card_shuffler program
+    #[account(mut)]
+    pub computation_account: UncheckedAccount<'info>,
+    /// CHECK: comp_def_account, checked by the card_shuffler program
+    pub comp_def_account: UncheckedAccount<'info>,
+    /// CHECK: cluster_account, checked by the card_shuffler program
+    #[account(mut)]
+    pub cluster_account: UncheckedAccount<'info>,
+    /// CHECK: pool_account, checked by the card_shuffler program
+    #[account(mut)]
+    pub pool_account: UncheckedAccount<'info>,
+    /// CHECK: clock_account, checked by the card_shuffler program
+    pub clock_account: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
+    /// CHECK: arcium_program, checked by the card_shuffler program
+    pub arcium_program: UncheckedAccount<'info>,
+    /// CHECK: card_game, checked (or created) by the card_shuffler program
+    #[account(mut)]
+    pub card_game: UncheckedAccount<'info>,
+}
+
+impl<'info> ShufflerAccounts<'info> {
+    fn to_cpi_accounts(&self) -> card_shuffler_cpi::accounts::QueueComputation<'info> {
+        card_shuffler_cpi::accounts::QueueComputation {
+            payer: self.shuffler_authority.to_account_info(),
+            authority: self.shuffler_authority.to_account_info(),
+            sign_pda_account: self.sign_pda_account.to_account_info(),
+            mxe_account: self.mxe_account.to_account_info(),
+            mempool_account: self.mempool_account.to_account_info(),
+            executing_pool: self.executing_pool.to_account_info(),
+            computation_account: self.computation_account.to_account_info(),
+            comp_def_account: self.comp_def_account.to_account_info(),
+            cluster_account: self.cluster_account.to_account_info(),
+            pool_account: self.pool_account.to_account_info(),
+            clock_account: self.clock_account.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            arcium_program: self.arcium_program.to_account_info(),
+            card_game: self.card_game.to_account_info(),
+        }
+    }
 }
 
 #[derive(Accounts)]
 pub struct StartHand<'info> {
     #[account(mut)]
     pub game_session: Account<'info, GameSession>,
+
+    #[account(
+        constraint = shuffler.shuffler_authority.key() == shuffler_authority_pda(&game_session),
+        constraint = shuffler.card_game.key() == get_card_game_pda(game_session.game_id).0,
+    )]
+    pub shuffler: ShufflerAccounts<'info>,
+
+    /// CHECK: the card_shuffler program
+    #[account(address = CARD_SHUFFLER_PROGRAM_ID)]
+    pub card_shuffler_program: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
 pub struct DealHoleCards<'info> {
     #[account(mut)]
     pub game_session: Account<'info, GameSession>,
+
+    #[account(
+        constraint = shuffler.shuffler_authority.key() == shuffler_authority_pda(&game_session),
+        constraint = shuffler.card_game.key() == get_card_game_pda(game_session.game_id).0,
+    )]
+    pub shuffler: ShufflerAccounts<'info>,
+
+    /// CHECK: the card_shuffler program
+    #[account(address = CARD_SHUFFLER_PROGRAM_ID)]
+    pub card_shuffler_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DealCommunityCards<'info> {
+    pub game_session: Account<'info, GameSession>,
+
+    /// CHECK: this program, registered for card_shuffler's completion callback
+    #[account(address = crate::ID)]
+    pub example_program: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = shuffler.shuffler_authority.key() == shuffler_authority_pda(&game_session),
+        constraint = shuffler.card_game.key() == get_card_game_pda(game_session.game_id).0,
+    )]
+    pub shuffler: ShufflerAccounts<'info>,
+
+    /// CHECK: the card_shuffler program
+    #[account(address = CARD_SHUFFLER_PROGRAM_ID)]
+    pub card_shuffler_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OnCardShufflerCallback<'info> {
+    /// Signs only when card_shuffler makes the call
+    #[account(address = notifier_pda().0)]
+    pub notifier: Signer<'info>,
+
+    #[account(address = get_card_game_pda(game_session.game_id).0)]
+    pub card_game: Box<Account<'info, CardGame>>,
+
+    #[account(mut)]
+    pub game_session: Account<'info, GameSession>,
 }
 
 #[derive(Accounts)]
@@ -227,7 +542,7 @@ pub struct RevealCommunityCards<'info> {
         bump,
         seeds::program = CARD_SHUFFLER_PROGRAM_ID,
     )]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
 }
 
 #[derive(Accounts)]
@@ -240,13 +555,36 @@ pub struct GetGameInfo<'info> {
         bump,
         seeds::program = CARD_SHUFFLER_PROGRAM_ID,
     )]
-    pub card_game: Account<'info, CardGame>,
+    pub card_game: Box<Account<'info, CardGame>>,
 }
 
 #[derive(Accounts)]
 pub struct EndHand<'info> {
     #[account(mut)]
     pub game_session: Account<'info, GameSession>,
+
+    #[account(
+        constraint = shuffler.shuffler_authority.key() == shuffler_authority_pda(&game_session),
+        constraint = shuffler.card_game.key() == get_card_game_pda(game_session.game_id).0,
+    )]
+    pub shuffler: ShufflerAccounts<'info>,
+
+    /// CHECK: the card_shuffler program
+    #[account(address = CARD_SHUFFLER_PROGRAM_ID)]
+    pub card_shuffler_program: UncheckedAccount<'info>,
+}
+
+/// Address of the session PDA that signs card_shuffler CPIs
+fn shuffler_authority_pda(game_session: &Account<GameSession>) -> Pubkey {
+    Pubkey::create_program_address(
+        &[
+            b"shuffler_authority",
+            game_session.key().as_ref(),
+            &[game_session.shuffler_authority_bump],
+        ],
+        &crate::ID,
+    )
+    .unwrap_or_default()
 }
 
 #[derive(Accounts)]
@@ -267,9 +605,9 @@ pub struct GameInfoEvent {
     pub game_id: u64,
     pub hand_number: u64,
     pub game_state: GameState,
-    pub hole_cards_size: u8,
+    pub num_seats: u8,
     pub community_cards_size: u8,
-    pub cards_dealt: u8,
+    pub cards_dealt: u16,
 }
 
 #[error_code]
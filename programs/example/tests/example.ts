@@ -6,6 +6,7 @@ import { Example } from "../target/types/example";
 import { expect } from "chai";
 import fs from "fs";
 import path from "path";
+import { createHash } from "crypto";
 
 describe("example - poker game integration", () => {
   const provider = anchor.AnchorProvider.env();
@@ -62,6 +63,58 @@ describe("example - poker game integration", () => {
       CARD_SHUFFLER_PROGRAM_ID
     );
 
+  const getShufflerAuthorityPda = (gameSession: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("shuffler_authority"), gameSession.toBuffer()],
+      program.programId
+    );
+
+  const ARCIUM_PROGRAM_ID = new anchor.web3.PublicKey(
+    "BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6"
+  );
+
+  const getCompDefOffset = (name: string) =>
+    createHash("sha256").update(name).digest().subarray(0, 4);
+
+  // Accounts forwarded to a card_shuffler instruction that queues a computation
+  const getShufflerAccounts = (
+    gameSession: anchor.web3.PublicKey,
+    computationOffset: anchor.BN,
+    compDefName: string
+  ) => ({
+    shufflerAuthority: getShufflerAuthorityPda(gameSession)[0],
+    signPdaAccount: getSignPda()[0],
+    mxeAccount: getArtifactPubkey("mxe_acc.json"),
+    mempoolAccount: getArtifactPubkey("mempool_acc.json"),
+    executingPool: getArtifactPubkey("executing_pool_acc.json"),
+    computationAccount: anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("ComputationAccount"),
+        CARD_SHUFFLER_PROGRAM_ID.toBuffer(),
+        computationOffset.toArrayLike(Buffer, "le", 8),
+      ],
+      ARCIUM_PROGRAM_ID
+    )[0],
+    compDefAccount: anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("ComputationDefinitionAccount"),
+        CARD_SHUFFLER_PROGRAM_ID.toBuffer(),
+        getCompDefOffset(compDefName),
+      ],
+      ARCIUM_PROGRAM_ID
+    )[0],
+    clusterAccount: getArtifactPubkey("cluster_acc_0.json"),
+    poolAccount: new anchor.web3.PublicKey(
+      "7MGSS4iKNM4sVib7bDZDJhVqB6EcchPwVnTKenCY1jt3"
+    ),
+    clockAccount: new anchor.web3.PublicKey(
+      "FHriyvoZotYiFnbUzKFjzRSb2NiaC8RPWY7jtKuKhg65"
+    ),
+    systemProgram: anchor.web3.SystemProgram.programId,
+    arciumProgram: ARCIUM_PROGRAM_ID,
+    cardGame: getCardGamePda(gameId)[0],
+  });
+
   const loadCardShuffler = () => {
     if (!RUN_ARCIUM) return null;
     const idlPath = path.join(arciumRoot, "target/idl/card_shuffler.json");
@@ -104,7 +157,10 @@ describe("example - poker game integration", () => {
     
     console.log("1️⃣  Creating game session...");
     await program.methods
-      .createGameSession(gameId)
+      .createGameSession(
+        gameId,
+        new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10) // funds card_shuffler fees and rent
+      )
       .accounts({
         player,
       })
@@ -117,181 +173,123 @@ describe("example - poker game integration", () => {
     console.log("   📍 Game Session PDA:", gameSessionPda.toBase58());
     console.log("   📍 Expected CardGame PDA:", cardGamePda.toBase58());
 
-    
-    console.log("\n2️⃣  Starting poker hand...");
+    if (!RUN_ARCIUM || !cardShuffler) {
+      console.log(
+        "\n   ⚠️  Skipping the hand (start_hand and deal_hole_cards CPI into card_shuffler; set RUN_ARCIUM=1 to enable)"
+      );
+      return;
+    }
+
+    console.log("\n2️⃣  Starting poker hand (CPI: initialize_card_game)...");
+    const mxeNonce = new anchor.BN(Date.now());
+    const saltNonce = new anchor.BN(Date.now() + 1);
+    const clientNonce = new anchor.BN(Date.now() + 2);
+    const clientPubkey = new Uint8Array(32).fill(7);
+    const startOffset = new anchor.BN(Date.now());
     await program.methods
-      .startHand()
+      .startHand(
+        startOffset,
+        mxeNonce,
+        saltNonce,
+        Array.from(clientPubkey),
+        clientNonce
+      )
       .accounts({
         gameSession: gameSessionPda,
-      })
-      .rpc();
+        shuffler: getShufflerAccounts(
+          gameSessionPda,
+          startOffset,
+          "shuffle_and_deal_deck"
+        ),
+      } as any)
+      .rpc({ skipPreflight: true });
 
     gameSession = await program.account.gameSession.fetch(gameSessionPda);
     expect(gameSession.gameState).to.deep.equal({ shufflingDeck: {} });
     expect(gameSession.handNumber.toString()).to.equal("1");
     console.log("   ✅ Hand #1 started");
-    console.log("   🎲 Game state: ShufflingDeck");
-    if (RUN_ARCIUM && cardShuffler) {
-      console.log("\n   🌐 Commissioning Arcium: initialize_card_game …");
-
-      const [signPda] = getSignPda();
-      const mxeAccount = getArtifactPubkey("mxe_acc.json");
-      const mempoolAccount = getArtifactPubkey("mempool_acc.json");
-      const executingPool = getArtifactPubkey("executing_pool_acc.json");
-      const clusterAccount = getArtifactPubkey("cluster_acc_0.json");
-      const poolAccount = new anchor.web3.PublicKey(
-        "7MGSS4iKNM4sVib7bDZDJhVqB6EcchPwVnTKenCY1jt3"
-      );
-      const clockAccount = new anchor.web3.PublicKey(
-        "FHriyvoZotYiFnbUzKFjzRSb2NiaC8RPWY7jtKuKhg65"
-      );
 
-      
-      const mxeNonce = new anchor.BN(Date.now());
-      const clientNonce = new anchor.BN(Date.now() + 1);
-      const clientPubkey = new Uint8Array(32).fill(7); 
-
-      
-      try {
-        await (cardShuffler as Program).methods
-          .initShuffleAndDealCompDef()
-          .accounts({
-            payer: player,
-            mxeAccount,
-            compDefAccount: anchor.web3.Keypair.generate().publicKey, 
-            arciumProgram: new anchor.web3.PublicKey(
-              "BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6"
-            ),
-            systemProgram: anchor.web3.SystemProgram.programId,
-          })
-          .rpc({ skipPreflight: true })
-          .catch(() => undefined);
-      } catch (_) {}
-
-      
-      await (cardShuffler as Program).methods
-        .initializeCardGame(
-          new anchor.BN(0),
-          new anchor.BN(gameId.toString()),
-          mxeNonce,
-          Array.from(clientPubkey),
-          clientNonce,
-          2
-        )
-        .accounts({
-          payer: player,
-          signPdaAccount: signPda,
-          mxeAccount,
-          mempoolAccount,
-          executingPool,
-          computationAccount: getArtifactPubkey("mxe_keygen_comp.json"), 
-          compDefAccount: getArtifactPubkey("mxe_keygen_comp_def.json"), 
-          clusterAccount,
-          poolAccount,
-          clockAccount,
-          systemProgram: anchor.web3.SystemProgram.programId,
-          arciumProgram: new anchor.web3.PublicKey(
-            "BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6"
-          ),
-          cardGame: cardGamePda,
-        })
-        .rpc({ skipPreflight: true });
-
-      
-      console.log("   ⏳ Waiting for MPC callback (hole cards)…");
-      const ok = await waitFor(
-        async () => {
-          const acctInfo = await provider.connection.getAccountInfo(
-            cardGamePda
-          );
-          if (!acctInfo) return false;
-          return true; 
-        },
-        { timeoutMs: 90_000, intervalMs: 2_000 }
-      );
-      if (!ok) throw new Error("Timed out waiting for CardGame account");
-      console.log("   ✅ CardGame initialized");
-    } else {
-      console.log(
-        "\n   ⚠️  Skipping Arcium commissioning (set RUN_ARCIUM=1 to enable)"
-      );
-      console.log(
-        "      → Would call card_shuffler.initialize_card_game(gameId, …)"
-      );
-    }
+    console.log("   ⏳ Waiting for MPC callback (shuffle)…");
+    const shuffled = await waitFor(
+      async () => {
+        const acctInfo = await provider.connection.getAccountInfo(cardGamePda);
+        return !!acctInfo;
+      },
+      { timeoutMs: 90_000, intervalMs: 2_000 }
+    );
+    if (!shuffled) throw new Error("Timed out waiting for CardGame account");
+    console.log("   ✅ CardGame initialized");
 
-    
-    console.log("\n3️⃣  Dealing hole cards...");
+    console.log("\n3️⃣  Dealing hole cards (CPI: store_hole_cards)...");
+    const dealOffset = new anchor.BN(Date.now());
     await program.methods
-      .dealHoleCards()
+      .dealHoleCards(dealOffset)
       .accounts({
         gameSession: gameSessionPda,
-      })
-      .rpc();
+        shuffler: getShufflerAccounts(
+          gameSessionPda,
+          dealOffset,
+          "store_hole_cards"
+        ),
+      } as any)
+      .rpc({ skipPreflight: true });
 
     gameSession = await program.account.gameSession.fetch(gameSessionPda);
     expect(gameSession.gameState).to.deep.equal({ holeCardsDealt: {} });
     console.log("   ✅ Hole cards dealt");
     console.log("   🎲 Game state: HoleCardsDealt");
-    if (!RUN_ARCIUM || !cardShuffler) {
-      console.log("\n   ⚠️  Skipping storeHoleCards (Arcium not enabled)");
-    }
 
-    
-    const doReveal = async (n: 1 | 3) => {
-      await (cardShuffler as Program).methods
-        .revealCommunityCards(
-          new anchor.BN(0),
-          new anchor.BN(gameId.toString()),
-          n
-        )
+    const doReveal = async (n: 1 | 3, expectedState: object) => {
+      const revealOffset = new anchor.BN(Date.now());
+      await program.methods
+        .dealCommunityCards(revealOffset, n)
         .accounts({
-          payer: player,
-          signPdaAccount: getSignPda()[0],
-          mxeAccount: getArtifactPubkey("mxe_acc.json"),
-          mempoolAccount: getArtifactPubkey("mempool_acc.json"),
-          executingPool: getArtifactPubkey("executing_pool_acc.json"),
-          computationAccount: getArtifactPubkey("mxe_keygen_comp.json"), 
-          compDefAccount: getArtifactPubkey("mxe_keygen_comp_def.json"), 
-          clusterAccount: getArtifactPubkey("cluster_acc_0.json"),
-          poolAccount: new anchor.web3.PublicKey(
-            "7MGSS4iKNM4sVib7bDZDJhVqB6EcchPwVnTKenCY1jt3"
-          ),
-          clockAccount: new anchor.web3.PublicKey(
-            "FHriyvoZotYiFnbUzKFjzRSb2NiaC8RPWY7jtKuKhg65"
-          ),
-          systemProgram: anchor.web3.SystemProgram.programId,
-          arciumProgram: new anchor.web3.PublicKey(
-            "BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6"
+          gameSession: gameSessionPda,
+          shuffler: getShufflerAccounts(
+            gameSessionPda,
+            revealOffset,
+            "reveal_community_cards"
           ),
-          cardGame: cardGamePda,
-        })
+        } as any)
         .rpc({ skipPreflight: true });
 
-      
-      await waitFor(
+      // card_shuffler's callback records a notification once the cards are
+      // revealed; delivering it calls on_card_shuffler_callback, which
+      // advances the session
+      const cardGamePda = getCardGamePda(gameId)[0];
+      const notified = await waitFor(
         async () => {
-          const acctInfo = await provider.connection.getAccountInfo(
+          const cardGame = await cardShuffler!.account.cardGame.fetch(
             cardGamePda
           );
-          return !!acctInfo;
+          return cardGame.pendingNotification !== null;
         },
         { timeoutMs: 45_000, intervalMs: 1500 }
       );
+      expect(notified).to.equal(true);
 
-      
-      await program.methods
-        .revealCommunityCards(n)
+      await cardShuffler!.methods
+        .deliverNotification(gameId)
         .accounts({
-          gameSession: gameSessionPda,
           cardGame: cardGamePda,
-        } as any)
-        .rpc();
+          notifier: anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("notifier")],
+            CARD_SHUFFLER_PROGRAM_ID
+          )[0],
+        })
+        .remainingAccounts([
+          { pubkey: program.programId, isSigner: false, isWritable: false },
+          { pubkey: gameSessionPda, isSigner: false, isWritable: true },
+        ])
+        .rpc({ commitment: "confirmed" });
+
+      const session = await program.account.gameSession.fetch(gameSessionPda);
+      expect(session.gameState).to.deep.equal(expectedState);
     };
 
     console.log("\n4️⃣  Revealing flop (3 cards)...");
     if (RUN_ARCIUM && cardShuffler) {
-      await doReveal(3);
+      await doReveal(3, { flop: {} });
       console.log("   ✅ Flop revealed via Arcium");
     } else {
       console.log("   ⚠️  Skipping (Arcium not enabled)");
@@ -299,7 +297,7 @@ describe("example - poker game integration", () => {
 
     console.log("\n5️⃣  Revealing turn (1 card)...");
     if (RUN_ARCIUM && cardShuffler) {
-      await doReveal(1);
+      await doReveal(1, { turn: {} });
       console.log("   ✅ Turn revealed via Arcium");
     } else {
       console.log("   ⚠️  Skipping (Arcium not enabled)");
@@ -307,13 +305,88 @@ describe("example - poker game integration", () => {
 
     console.log("\n6️⃣  Revealing river (1 card)...");
     if (RUN_ARCIUM && cardShuffler) {
-      await doReveal(1);
+      await doReveal(1, { river: {} });
       console.log("   ✅ River revealed via Arcium");
     } else {
       console.log("   ⚠️  Skipping (Arcium not enabled)");
     }
 
-    console.log("\n7️⃣  Poker game flow complete!");
+    console.log("\n7️⃣  Ending hand #1 (CPI: clear_board, change_hand)...");
+    const endOffset = new anchor.BN(Date.now());
+    await program.methods
+      .endHand(endOffset, new anchor.BN(Date.now() + 1))
+      .accounts({
+        gameSession: gameSessionPda,
+        shuffler: getShufflerAccounts(gameSessionPda, endOffset, "change_hand"),
+      } as any)
+      .rpc({ skipPreflight: true });
+
+    gameSession = await program.account.gameSession.fetch(gameSessionPda);
+    expect(gameSession.gameState).to.deep.equal({ waitingToShuffle: {} });
+
+    // The board is cleared right away; the player's hand once change_hand's
+    // callback lands
+    const cleared = await waitFor(
+      async () => {
+        const cardGame = await cardShuffler.account.cardGame.fetch(cardGamePda);
+        return (
+          cardGame.communityCardsSize === 0 &&
+          cardGame.seats[0].holeCardsSize === 0 &&
+          cardGame.pendingComputation === null
+        );
+      },
+      { timeoutMs: 45_000, intervalMs: 1500 }
+    );
+    if (!cleared) throw new Error("Timed out waiting for the hand to reset");
+    console.log("   ✅ Board and hand cleared");
+
+    console.log("\n8️⃣  Playing hand #2 from the same deck...");
+    const nextOffset = new anchor.BN(Date.now());
+    await program.methods
+      .startHand(
+        nextOffset,
+        mxeNonce,
+        saltNonce,
+        Array.from(clientPubkey),
+        clientNonce
+      )
+      .accounts({
+        gameSession: gameSessionPda,
+        shuffler: getShufflerAccounts(
+          gameSessionPda,
+          nextOffset,
+          "shuffle_and_deal_deck"
+        ),
+      } as any)
+      .rpc({ skipPreflight: true });
+
+    gameSession = await program.account.gameSession.fetch(gameSessionPda);
+    expect(gameSession.gameState).to.deep.equal({ shufflingDeck: {} });
+    expect(gameSession.handNumber.toString()).to.equal("2");
+
+    const secondDealOffset = new anchor.BN(Date.now());
+    await program.methods
+      .dealHoleCards(secondDealOffset)
+      .accounts({
+        gameSession: gameSessionPda,
+        shuffler: getShufflerAccounts(
+          gameSessionPda,
+          secondDealOffset,
+          "store_hole_cards"
+        ),
+      } as any)
+      .rpc({ skipPreflight: true });
+
+    await doReveal(3, { flop: {} });
+    await doReveal(1, { turn: {} });
+    await doReveal(1, { river: {} });
+
+    const secondHand = await cardShuffler.account.cardGame.fetch(cardGamePda);
+    expect(secondHand.communityCardsSize).to.equal(5);
+    expect(secondHand.seats[0].holeCardsSize).to.equal(2);
+    console.log("   ✅ Hand #2 dealt a fresh board of 5 cards");
+
+    console.log("\n9️⃣  Poker game flow complete!");
     console.log("   💡 At this point:");
     console.log("      • CardGame.community_cards has 5 revealed cards");
     console.log("      • CardGame.hole_cards has encrypted player cards");
@@ -402,9 +475,9 @@ describe("example - poker game integration", () => {
     console.log("\nℹ️  To end a hand:");
     console.log("  1. Game must be in River state");
     console.log("  2. Call poker.endHand()");
-    console.log("  3. Game state resets to WaitingToShuffle");
-    console.log("  4. Call card_shuffler.changeHand() to deal new cards");
+    console.log("  3. poker CPIs card_shuffler.change_hand to clear the hand");
     console.log("     (This updates hole cards without reshuffling full deck)");
+    console.log("  4. Game state resets to WaitingToShuffle");
   });
 
   it("Closes the game session", async () => {